@@ -16,6 +16,7 @@ to output a [`Module`](crate::Module) into glsl
 ### ES
 - 300
 - 310
+- 320
 
 [glsl]: https://www.khronos.org/registry/OpenGL/index_gl.php
 */
@@ -77,6 +78,11 @@ mod keywords;
 /// List of supported `core` GLSL versions.
 pub const SUPPORTED_CORE_VERSIONS: &[u16] = &[140, 150, 330, 400, 410, 420, 430, 440, 450, 460];
 /// List of supported `es` GLSL versions.
+///
+/// 320 is the top ES version: several things that require an `#extension` on
+/// 310 (e.g. compute shaders, `gl_Layer` writes from the vertex stage) are
+/// core on 320, so the features manager should treat them as such and skip
+/// the now-unnecessary extension line.
 pub const SUPPORTED_ES_VERSIONS: &[u16] = &[300, 310, 320];
 
 /// The suffix of the variable that will hold the calculated clamped level
@@ -89,12 +95,31 @@ pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
 // Must match code in glsl_built_in
 pub const FIRST_INSTANCE_BINDING: &str = "naga_vs_first_instance";
 
+/// The lowest number of varying (`in`/`out`) locations guaranteed by every
+/// GLSL version and profile this backend supports, per
+/// `GL_MAX_VARYING_COMPONENTS`/`GL_MAX_VARYING_VECTORS` in the relevant specs.
+/// Used to flag varying interfaces that may not link consistently across
+/// drivers with only the minimum guaranteed budget.
+///
+/// This only catches over-allocation against that floor; it doesn't assign or
+/// pack locations, and doesn't check that a vertex stage's `out` locations
+/// agree with a separately-compiled fragment stage's `in` locations. Naga's
+/// IR always carries explicit, author-assigned [`Binding::Location`]s by the
+/// time they reach this backend (nothing here invents one), so true
+/// cross-stage packing/matching would need a shared allocator consulted by
+/// both [`Writer`] instances at the point locations are assigned upstream,
+/// not something addressable from a single stage's [`write_varying`] call.
+///
+/// [`Binding::Location`]: crate::Binding::Location
+/// [`write_varying`]: Writer::write_varying
+const MIN_GUARANTEED_VARYING_LOCATIONS: u32 = 15;
+
 #[cfg(any(feature = "serialize", feature = "deserialize"))]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 struct BindingMapSerialization {
     resource_binding: crate::ResourceBinding,
-    bind_target: u8,
+    bind_target: BindTarget,
 }
 
 #[cfg(feature = "deserialize")]
@@ -112,8 +137,35 @@ where
     Ok(map)
 }
 
+/// A target to bind a resource to.
+///
+/// On GL-style targets only [`Self::binding`] is meaningful and [`Self::set`]
+/// is ignored. When [`WriterFlags::VULKAN_GLSL`] is set, both fields drive
+/// the emitted `layout(set = .., binding = ..)` qualifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct BindTarget {
+    /// The Vulkan descriptor set. Ignored unless `VULKAN_GLSL` is set.
+    pub set: u8,
+    /// The binding index within the descriptor set (or, on GL, the bare `binding`).
+    pub binding: u8,
+}
+
+impl From<u8> for BindTarget {
+    fn from(binding: u8) -> Self {
+        BindTarget { set: 0, binding }
+    }
+}
+
+impl fmt::Display for BindTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.binding)
+    }
+}
+
 /// Mapping between resources and bindings.
-pub type BindingMap = alloc::collections::BTreeMap<crate::ResourceBinding, u8>;
+pub type BindingMap = alloc::collections::BTreeMap<crate::ResourceBinding, BindTarget>;
 
 impl crate::AtomicFunction {
     const fn to_glsl(self) -> &'static str {
@@ -125,7 +177,10 @@ impl crate::AtomicFunction {
             Self::Min => "Min",
             Self::Max => "Max",
             Self::Exchange { compare: None } => "Exchange",
-            Self::Exchange { compare: Some(_) } => "", //TODO
+            // Compare-exchange has no single GLSL built-in of the form `atomic{name}`:
+            // it's written out as a full `atomicCompSwap` call at each use site instead
+            // (see the `Statement::Atomic` writer), so callers never append this string.
+            Self::Exchange { compare: Some(_) } => "",
         }
     }
 }
@@ -223,6 +278,14 @@ impl Version {
         *self >= Version::Desktop(400) || *self >= Version::new_gles(320)
     }
 
+    /// Returns true if the version can express multiview rendering through
+    /// `GL_OVR_multiview2`'s `layout(num_views = N) in;` and `gl_ViewID_OVR`,
+    /// needed on targets other than WebGL (which gets multiview implicitly
+    /// through the WebXR contract instead of an explicit extension).
+    fn supports_multiview(&self) -> bool {
+        *self >= Version::Desktop(330) || *self >= Version::new_gles(300)
+    }
+
     fn supports_integer_functions(&self) -> bool {
         *self >= Version::Desktop(400) || *self >= Version::new_gles(310)
     }
@@ -235,6 +298,20 @@ impl Version {
         *self >= Version::Desktop(450)
     }
 
+    /// Returns true if the version supports `atomicCompSwap` on buffer/shared
+    /// atomics, required to express WGSL's `atomicCompareExchangeWeak`.
+    fn supports_atomic_compare_exchange(&self) -> bool {
+        *self >= Version::Desktop(430) || *self >= Version::new_gles(310)
+    }
+
+    /// Returns true if the version is new enough to be targeted by the
+    /// `GL_KHR_shader_subgroup_*` extension family, required by subgroup
+    /// operations. Per the extension spec, it's written against desktop
+    /// GL 4.3 and GLES 3.1.
+    fn supports_subgroup_operations(&self) -> bool {
+        *self >= Version::Desktop(430) || *self >= Version::new_gles(310)
+    }
+
     // For supports_pack_unpack_4x8, supports_pack_unpack_snorm_2x16, supports_pack_unpack_unorm_2x16
     // see:
     // https://registry.khronos.org/OpenGL-Refpages/gl4/html/unpackUnorm.xhtml
@@ -258,6 +335,40 @@ impl Version {
     fn supports_pack_unpack_half_2x16(&self) -> bool {
         *self >= Version::Desktop(420) || *self >= Version::new_gles(300)
     }
+
+    /// Returns true if the version can be targeted by `GL_ARB_gpu_shader_int64`,
+    /// which provides the `int64_t`/`uint64_t` scalar types (and their vector
+    /// forms). Desktop-only; GLES has no equivalent extension.
+    fn supports_shader_int64(&self) -> bool {
+        *self >= Version::Desktop(400)
+    }
+
+    /// Returns true if the version can be targeted by
+    /// `GL_EXT_shader_explicit_arithmetic_types_float16`, which provides the
+    /// `float16_t` scalar type (and its vector/matrix forms). Unlike the
+    /// 64-bit integer extension this one is also available on ES, just not
+    /// on profiles older than 3.10.
+    fn supports_shader_float16(&self) -> bool {
+        !self.is_es() || *self >= Version::new_gles(310)
+    }
+
+    /// Returns true if `double`/`dvec*`/`dmat*` are natively available,
+    /// either because they're core (GLSL >= 4.00) or because
+    /// `GL_ARB_gpu_shader_fp64` can be required. Desktop-only; no ES profile
+    /// has any form of double precision support.
+    fn supports_fp64(&self) -> bool {
+        *self >= Version::Desktop(150)
+    }
+
+    /// Returns true if the version can be targeted by
+    /// `GL_EXT_shader_image_int64`, which provides 64-bit storage-image
+    /// atomics (`r64ui` images, plus `int64_t`/`uint64_t` overloads of
+    /// `imageAtomic*`). Built on the same `int64_t`/`uint64_t` scalar types
+    /// as `GL_ARB_gpu_shader_int64`, so it shares that extension's
+    /// desktop-only floor.
+    fn supports_shader_image_int64(&self) -> bool {
+        self.supports_shader_int64()
+    }
 }
 
 impl PartialOrd for Version {
@@ -306,6 +417,33 @@ bitflags::bitflags! {
         /// The variable gl_PointSize is intended for a shader to write the size of the point to be rasterized. It is measured in pixels.
         /// If gl_PointSize is not written to, its value is undefined in subsequent pipe stages.
         const FORCE_POINT_SIZE = 0x20;
+        /// Target Vulkan-consumable GLSL (via `GL_KHR_vulkan_glsl`) instead of
+        /// GL-style GLSL: samplers are emitted as separate `textureND`/`sampler`
+        /// declarations with explicit `layout(set = .., binding = ..)`
+        /// qualifiers rather than a single combined `gsamplerN`.
+        const VULKAN_GLSL = 0x40;
+        /// Force the `coherent` memory qualifier onto every storage buffer and
+        /// image global, on top of whatever `readonly`/`writeonly` is derived
+        /// from the global's [`StorageAccess`](crate::StorageAccess).
+        const FORCE_COHERENT_STORAGE = 0x80;
+        /// Force the `restrict` memory qualifier onto every storage buffer and
+        /// image global, promising the driver that no two storage bindings in
+        /// the shader alias.
+        const FORCE_RESTRICT_STORAGE = 0x100;
+        /// Force the `volatile` memory qualifier onto every storage buffer and
+        /// image global, disabling compiler caching of loads/stores so that
+        /// writes from other invocations are always observed.
+        const FORCE_VOLATILE_STORAGE = 0x200;
+        /// Supports `GL_ARB_gpu_shader_int64` on the host, which provides the
+        /// `int64_t`/`uint64_t` scalar types (and vector forms) along with
+        /// `L`/`UL`-suffixed 64-bit integer literals. Without this, 64-bit
+        /// integer literals/types are rejected with an error.
+        const SHADER_INT64 = 0x400;
+        /// Supports `GL_EXT_shader_explicit_arithmetic_types_float16` on the
+        /// host, which provides the `float16_t` scalar type (and vector/matrix
+        /// forms) along with `hf`-suffixed half-precision float literals.
+        /// Without this, `Literal::F16` is rejected with an error.
+        const SHADER_FLOAT16 = 0x800;
     }
 }
 
@@ -325,8 +463,45 @@ pub struct Options {
         serde(deserialize_with = "deserialize_binding_map")
     )]
     pub binding_map: BindingMap,
+    /// Per-entry-point override of [`Self::binding_map`], keyed by entry point name.
+    ///
+    /// When a [`Writer`] is created for a given [`PipelineOptions::entry_point`], the
+    /// map with that name is used in place of [`Self::binding_map`] if one is present.
+    /// This lets a single [`Module`](crate::Module) that defines several entry points
+    /// of the same stage be reused across pipelines with differing descriptor layouts.
+    pub per_entry_point_binding_map: alloc::collections::BTreeMap<String, BindingMap>,
+    /// Default precision qualifiers emitted for ES targets.
+    ///
+    /// Ignored on `core` profiles, where precision qualifiers are a no-op and
+    /// nothing is emitted.
+    pub precision: PrecisionQualifiers,
+    /// Per-global override of [`Self::precision`]'s float precision, keyed by
+    /// the [`GlobalVariable`](crate::GlobalVariable) handle. Used for sampler
+    /// and image declarations that need a precision differing from the
+    /// default (e.g. a `lowp` video texture sampled alongside `highp` ones).
+    pub precision_overrides: alloc::collections::BTreeMap<Handle<crate::GlobalVariable>, Precision>,
+    /// When set, emulate push constants as a `std140` uniform block bound at
+    /// this slot, instead of the default plain-global lowering.
+    ///
+    /// This gives the block a stable name the host can look up once and
+    /// update in full with a single `glBufferSubData`, rather than issuing
+    /// one `glUniform*` call per push-constant member. Leave unset to keep
+    /// the plain-global path, which remains valid GLSL everywhere.
+    pub push_constant_binding: Option<BindTarget>,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
+    /// When set, [`collect_push_constant_items`](Writer::collect_push_constant_items)
+    /// emits the items of an array's `[0]` element only (one item, or one per leaf
+    /// field when the element is a struct), tagged with [`PushConstantItem::array`],
+    /// instead of unrolling it into one item per element. For an array of arrays
+    /// only the outermost array's count and stride are recorded.
+    ///
+    /// A large array (e.g. a `mat4[64]` bone palette) would otherwise produce
+    /// thousands of reflection entries; in compact mode the client instead issues one
+    /// `glUniform*` call covering the whole array, starting at the `[0]` element's
+    /// location, per GL's `name[0]` array-upload semantics. Leave unset to keep the
+    /// unrolled, per-element names some backends/drivers still need.
+    pub compact_push_constant_arrays: bool,
 }
 
 impl Default for Options {
@@ -335,11 +510,124 @@ impl Default for Options {
             version: Version::new_gles(310),
             writer_flags: WriterFlags::ADJUST_COORDINATE_SPACE,
             binding_map: BindingMap::default(),
+            per_entry_point_binding_map: alloc::collections::BTreeMap::new(),
+            precision: PrecisionQualifiers::default(),
+            precision_overrides: alloc::collections::BTreeMap::new(),
+            push_constant_binding: None,
             zero_initialize_workgroup_memory: true,
+            compact_push_constant_arrays: false,
+        }
+    }
+}
+
+/// A GLSL ES precision qualifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub enum Precision {
+    High,
+    Medium,
+    Low,
+}
+
+impl Precision {
+    /// The GLSL keyword for this precision qualifier.
+    const fn as_str(self) -> &'static str {
+        match self {
+            Precision::High => "highp",
+            Precision::Medium => "mediump",
+            Precision::Low => "lowp",
+        }
+    }
+}
+
+/// Default precision qualifiers for GLSL ES output, keyed by scalar kind.
+///
+/// Mobile/WebGL targets frequently need `mediump`/`lowp` for performance, or
+/// to match the precision behavior of hand-written shaders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct PrecisionQualifiers {
+    /// Default precision for `float` (and `floatN`/`matNxM`) declarations.
+    pub float: Precision,
+    /// Default precision for `int`/`uint` (and vector) declarations.
+    pub int: Precision,
+}
+
+impl Default for PrecisionQualifiers {
+    fn default() -> Self {
+        PrecisionQualifiers {
+            float: Precision::High,
+            int: Precision::High,
         }
     }
 }
 
+/// Computes a deterministic cache key identifying the GLSL (and its
+/// [`ReflectionInfo`]) that writing `pipeline_options.entry_point` from
+/// `module` with `options` would produce.
+///
+/// Two calls with equal `module`, `options`, and `pipeline_options` always
+/// yield the same key, including across processes, so callers that cache
+/// compiled GL program binaries (e.g. via `glGetProgramBinary`/
+/// `glProgramBinary`) can fold this into their own cache key instead of
+/// re-translating the shader to check for a match.
+///
+/// The bounds-check policies passed to [`Writer::new`] are not part of the
+/// key.
+pub fn reflection_cache_key(
+    module: &crate::Module,
+    options: &Options,
+    pipeline_options: &PipelineOptions,
+) -> u64 {
+    // A small FNV-1a implementation: deterministic across platforms/processes
+    // and doesn't require pulling in a hashing crate dependency just for this.
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write_bytes = |bytes: &[u8], hash: &mut u64| {
+        for &byte in bytes {
+            *hash ^= u64::from(byte);
+            *hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+
+    // The module's `Debug` output is stable across calls with an identical
+    // module, since the IR is stored in insertion-ordered arenas rather than
+    // unordered maps.
+    write_bytes(format!("{module:?}").as_bytes(), &mut hash);
+    write_bytes(format!("{:?}", pipeline_options.shader_stage).as_bytes(), &mut hash);
+    write_bytes(pipeline_options.entry_point.as_bytes(), &mut hash);
+    write_bytes(
+        &pipeline_options
+            .multiview
+            .map_or(0, core::num::NonZeroU32::get)
+            .to_le_bytes(),
+        &mut hash,
+    );
+    write_bytes(format!("{:?}", options.version).as_bytes(), &mut hash);
+    write_bytes(&options.writer_flags.bits().to_le_bytes(), &mut hash);
+    write_bytes(format!("{:?}", options.binding_map).as_bytes(), &mut hash);
+    write_bytes(
+        format!("{:?}", options.per_entry_point_binding_map).as_bytes(),
+        &mut hash,
+    );
+    write_bytes(format!("{:?}", options.precision).as_bytes(), &mut hash);
+    write_bytes(
+        format!("{:?}", options.precision_overrides).as_bytes(),
+        &mut hash,
+    );
+    write_bytes(
+        format!("{:?}", options.push_constant_binding).as_bytes(),
+        &mut hash,
+    );
+    write_bytes(&[options.zero_initialize_workgroup_memory as u8], &mut hash);
+    write_bytes(&[options.compact_push_constant_arrays as u8], &mut hash);
+
+    hash
+}
+
 /// A subset of options meant to be changed per pipeline.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
@@ -357,6 +645,8 @@ pub struct PipelineOptions {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct VaryingLocation {
     /// The location of the global.
     /// This corresponds to `layout(location = ..)` in GLSL.
@@ -367,7 +657,13 @@ pub struct VaryingLocation {
 }
 
 /// Reflection info for texture mappings and uniforms.
+///
+/// This is serializable so that it can be persisted alongside a cached
+/// `glGetProgramBinary` blob (see [`reflection_cache_key`]) and reloaded
+/// without re-translating the shader.
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct ReflectionInfo {
     /// Mapping between texture names and variables/samplers.
     pub texture_mapping: crate::FastHashMap<String, TextureMapping>,
@@ -379,6 +675,25 @@ pub struct ReflectionInfo {
     pub push_constant_items: Vec<PushConstantItem>,
     /// Number of user-defined clip planes. Only applicable to vertex shaders.
     pub clip_distance_count: u32,
+    /// Maps each used global that has a resource binding (buffers, textures
+    /// and samplers alike) back to the
+    /// Naga `(group, binding)` pair it was declared with, so callers don't
+    /// have to parse it back out of the generated `_group_X_binding_Y_stage`
+    /// name.
+    pub resource_bindings: crate::FastHashMap<Handle<crate::GlobalVariable>, crate::ResourceBinding>,
+    /// Globals whose interface block had its dynamically-sized array member
+    /// lifted to the top level of the block (see `write_interface_block`),
+    /// rather than wrapped in a single-member struct. Callers need this to
+    /// know whether to address block members directly or through the block's
+    /// sole struct member.
+    pub lifted_interface_blocks: alloc::collections::BTreeSet<Handle<crate::GlobalVariable>>,
+    /// Per-member std140/std430 layout for every `Uniform`/`Storage` buffer
+    /// global that also appears in [`Self::uniforms`], keyed the same way.
+    /// See [`BufferReflectionItem`]. Lets a client upload data into a mapped
+    /// buffer at the right offsets without re-deriving the layout rules
+    /// itself, the same way [`Self::push_constant_items`] does for push
+    /// constants.
+    pub buffer_reflection: crate::FastHashMap<Handle<crate::GlobalVariable>, Vec<BufferReflectionItem>>,
 }
 
 /// Mapping between a texture and its sampler, if it exists.
@@ -391,11 +706,40 @@ pub struct ReflectionInfo {
 /// [`Storage`](crate::ImageClass::Storage) images produce `gimageN` and don't have an associated sampler,
 /// so the [`sampler`](Self::sampler) field will be [`None`].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct TextureMapping {
     /// Handle to the image global variable.
     pub texture: Handle<crate::GlobalVariable>,
     /// Handle to the associated sampler global variable, if it exists.
     pub sampler: Option<Handle<crate::GlobalVariable>>,
+    /// The image's GLSL dimension suffix (e.g. `"2D"`, `"Cube"`), as used in
+    /// `sampler2D`/`image2D`/etc. See [`glsl_dimension`].
+    pub dim: &'static str,
+    /// Whether the image is an array of [`Self::dim`]-dimensional images.
+    pub arrayed: bool,
+    /// Whether the image is multisampled.
+    pub multi: bool,
+    /// Whether this is a sampled, depth, or storage image, mirroring
+    /// [`crate::ImageClass`].
+    pub class: TextureMappingClass,
+}
+
+/// Whether a [`TextureMapping`] refers to a sampled, depth, or storage image.
+/// Mirrors [`crate::ImageClass`], minus the fields already exposed directly
+/// on [`TextureMapping`] (`multi`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub enum TextureMappingClass {
+    /// A regular sampled texture, bound together with a sampler.
+    Sampled,
+    /// A depth texture, sampled with a comparison sampler.
+    Depth,
+    /// A storage image, with its resolved GLSL `layout` format qualifier
+    /// (e.g. `"rgba8"`), or `None` if [`glsl_storage_format`] couldn't
+    /// resolve one.
+    Storage { format: Option<&'static str> },
 }
 
 /// All information to bind a single uniform value to the shader.
@@ -407,6 +751,8 @@ pub struct TextureMapping {
 /// we must do the work of calculating the offset of each primitive in the
 /// push constant block.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
 pub struct PushConstantItem {
     /// GL uniform name for the item. This name is the same as if you were
     /// to access it directly from a GLSL shader.
@@ -440,6 +786,51 @@ pub struct PushConstantItem {
     ///
     /// The size of the uniform can be derived from the type.
     pub offset: u32,
+    /// Set when [`Options::compact_push_constant_arrays`] is enabled and this item
+    /// represents a whole array rather than a single element: [`Self::access_path`]
+    /// then points at the array's `[0]` element (per GL's `name[0]` array-upload
+    /// semantics) and this field gives the element count and stride needed to upload
+    /// the rest in one call. `None` for a non-array item, or when compact mode is off
+    /// (in which case every element gets its own flat item instead).
+    pub array: Option<PushConstantArrayInfo>,
+}
+
+/// Element count and stride for a [`PushConstantItem`] representing a whole array.
+/// See [`PushConstantItem::array`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct PushConstantArrayInfo {
+    /// Number of elements in the array.
+    pub count: u32,
+    /// Byte stride between consecutive elements.
+    pub stride: u32,
+}
+
+/// A single scalar/vector/matrix member of a `Uniform`/`Storage` buffer,
+/// laid out according to the std140 (uniform) or std430 (storage) rules, as
+/// computed by [`proc::Layouter`]. Analogous to [`PushConstantItem`], but
+/// covering the full struct/array nesting and dynamically-sized arrays that
+/// real buffers (unlike push constants) can contain.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct BufferReflectionItem {
+    /// GLSL access path for the member, e.g. `ubo.lights[3].color`, rooted
+    /// at the identifier the buffer's members are reached through in the
+    /// generated GLSL. For an anonymous interface block this differs from the
+    /// block name recorded in [`ReflectionInfo::uniforms`].
+    pub access_path: String,
+    /// Type of the member. This will only ever be a scalar, vector, or matrix.
+    pub ty: Handle<crate::Type>,
+    /// The member's byte offset within the buffer.
+    pub offset: u32,
+    /// The byte stride between consecutive elements, if this member sits
+    /// inside an array (constant- or dynamically-sized). `None` otherwise.
+    pub array_stride: Option<u32>,
+    /// The byte stride between consecutive columns, if this member is a
+    /// matrix. `None` otherwise.
+    pub matrix_stride: Option<u32>,
 }
 
 /// Helper structure that generates a number
@@ -460,7 +851,6 @@ impl IdGenerator {
 #[derive(Clone, Copy)]
 struct VaryingOptions {
     output: bool,
-    targeting_webgl: bool,
     draw_parameters: bool,
 }
 
@@ -468,7 +858,6 @@ impl VaryingOptions {
     const fn from_writer_options(options: &Options, output: bool) -> Self {
         Self {
             output,
-            targeting_webgl: options.version.is_webgl(),
             draw_parameters: options.writer_flags.contains(WriterFlags::DRAW_PARAMETERS),
         }
     }
@@ -592,6 +981,10 @@ pub struct Writer<'a, W> {
     out: W,
     /// User defined configuration to be used.
     options: &'a Options,
+    /// The binding map selected for the chosen entry point: either the
+    /// entry in [`Options::per_entry_point_binding_map`] matching
+    /// [`Self::entry_point`]'s name, or [`Options::binding_map`] otherwise.
+    binding_map: &'a BindingMap,
     /// The bound checking policies to be used
     policies: proc::BoundsCheckPolicies,
 
@@ -625,6 +1018,14 @@ pub struct Writer<'a, W> {
     varying: crate::FastHashMap<String, VaryingLocation>,
     /// Number of user-defined clip planes. Only non-zero for vertex shaders.
     clip_distance_count: u32,
+    /// Extensions required by constructs this backend emits that aren't core
+    /// on the target version, collected by [`Self::collect_required_extensions`]
+    /// ahead of the main body so they can be flushed right after `#version`.
+    required_extensions: alloc::collections::BTreeSet<&'static str>,
+    /// Globals whose interface block lifted a dynamically-sized array member
+    /// to the top level instead of wrapping it in a single-member struct.
+    /// Carried into [`ReflectionInfo::lifted_interface_blocks`].
+    lifted_interface_blocks: alloc::collections::BTreeSet<Handle<crate::GlobalVariable>>,
 }
 
 impl<'a, W: Write> Writer<'a, W> {
@@ -672,12 +1073,18 @@ impl<'a, W: Write> Writer<'a, W> {
             &mut names,
         );
 
+        let binding_map = options
+            .per_entry_point_binding_map
+            .get(&pipeline_options.entry_point)
+            .unwrap_or(&options.binding_map);
+
         // Build the instance
         let mut this = Self {
             module,
             info,
             out,
             options,
+            binding_map,
             policies,
 
             namer,
@@ -693,14 +1100,310 @@ impl<'a, W: Write> Writer<'a, W> {
             continue_ctx: back::continue_forward::ContinueCtx::default(),
             varying: Default::default(),
             clip_distance_count: 0,
+            required_extensions: alloc::collections::BTreeSet::new(),
+            lifted_interface_blocks: alloc::collections::BTreeSet::new(),
         };
 
         // Find all features required to print this module
         this.collect_required_features()?;
+        this.collect_required_extensions()?;
 
         Ok(this)
     }
 
+    /// Scans the module for constructs this backend emits that need an
+    /// explicit `#extension` (on ES/WebGL2 targets: dual-source blending,
+    /// `gl_ClipDistance`, and the 8-bit packed dot/pack built-ins; on any
+    /// target: subgroup operations, ray queries, 64-bit integers, 16-bit
+    /// floats, `double` below GLSL 4.00, and 64-bit storage images), returns
+    /// an error when the target can't provide one of them, and records the
+    /// extension names in [`Self::required_extensions`] so
+    /// `write` can flush them right after `#version`, before anything that
+    /// depends on them is emitted.
+    fn collect_required_extensions(&mut self) -> Result<(), Error> {
+        if self.options.version.is_es() {
+            if let Some(ref result) = self.entry_point.function.result {
+                match result.binding {
+                    Some(crate::Binding::Location {
+                        blend_src: Some(_),
+                        ..
+                    }) => {
+                        self.required_extensions.insert("GL_EXT_blend_func_extended");
+                    }
+                    Some(crate::Binding::BuiltIn(crate::BuiltIn::ClipDistance)) => {
+                        self.required_extensions.insert("GL_EXT_clip_cull_distance");
+                    }
+                    _ => {}
+                }
+                // Dual-source outputs and `gl_ClipDistance` are normally members
+                // of a struct result, so check the members' bindings as well.
+                if let TypeInner::Struct { ref members, .. } = self.module.types[result.ty].inner {
+                    for binding in members.iter().filter_map(|member| member.binding.as_ref()) {
+                        match *binding {
+                            crate::Binding::Location { blend_src: Some(_), .. } => {
+                                self.required_extensions.insert("GL_EXT_blend_func_extended");
+                            }
+                            crate::Binding::BuiltIn(crate::BuiltIn::ClipDistance) => {
+                                self.required_extensions.insert("GL_EXT_clip_cull_distance");
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            let functions = self
+                .module
+                .functions
+                .iter()
+                .map(|(_, function)| function)
+                .chain(core::iter::once(&self.entry_point.function));
+            for function in functions {
+                for (_, expr) in function.expressions.iter() {
+                    if let crate::Expression::Math { fun, .. } = *expr {
+                        match fun {
+                            crate::MathFunction::Dot4U8Packed
+                            | crate::MathFunction::Dot4I8Packed
+                            | crate::MathFunction::Pack4xU8
+                            | crate::MathFunction::Pack4xI8
+                            | crate::MathFunction::Pack4xU8Clamp
+                            | crate::MathFunction::Pack4xI8Clamp => {
+                                self.required_extensions
+                                    .insert("GL_EXT_shader_explicit_arithmetic_types_int8");
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        // Subgroup operations need the `GL_KHR_shader_subgroup_*` extension family
+        // on both desktop GL and GLES, and ray queries need `GL_EXT_ray_query`,
+        // so this runs regardless of `is_es()`.
+        let mut needs = StatementExtensionNeeds::default();
+        let functions = self
+            .module
+            .functions
+            .iter()
+            .map(|(_, function)| function)
+            .chain(core::iter::once(&self.entry_point.function));
+        for function in functions {
+            for block_stmt in function.body.iter() {
+                collect_statement_extension_needs(block_stmt, &mut needs);
+            }
+        }
+        if !needs.subgroup_families.is_empty() {
+            if !self.options.version.supports_subgroup_operations() {
+                return Err(Error::VersionNotSupported);
+            }
+            // `basic` is a dependency of every other subgroup extension.
+            self.required_extensions
+                .insert("GL_KHR_shader_subgroup_basic");
+            self.required_extensions.extend(needs.subgroup_families);
+        }
+        if needs.uses_ray_query {
+            if self.options.version.is_es()
+                || !self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL)
+            {
+                return Err(Error::Custom(
+                    "ray queries are only supported when targeting Vulkan GLSL".to_owned(),
+                ));
+            }
+            self.required_extensions.insert("GL_EXT_ray_query");
+        }
+
+        // 64-bit integer literals/types need `GL_ARB_gpu_shader_int64`, opted
+        // into via `WriterFlags::SHADER_INT64` since there's no way to detect
+        // host support for it from the module alone.
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::SHADER_INT64)
+        {
+            // As with `SHADER_FLOAT16` below, a module can use `int64_t`/`i64vec*`
+            // purely through typed values (e.g. a function argument) with no `I64`/
+            // `U64` literal anywhere, so the type arena needs scanning too, not
+            // just expressions.
+            let uses_int64 = self.module.types.iter().any(|(_, ty)| {
+                matches!(
+                    ty.inner,
+                    TypeInner::Scalar(crate::Scalar { kind: crate::ScalarKind::Sint | crate::ScalarKind::Uint, width: 8 })
+                        | TypeInner::Vector { scalar: crate::Scalar { kind: crate::ScalarKind::Sint | crate::ScalarKind::Uint, width: 8 }, .. }
+                )
+            }) || self
+                .module
+                .global_expressions
+                .iter()
+                .chain(
+                    self.module
+                        .functions
+                        .iter()
+                        .map(|(_, function)| function)
+                        .chain(core::iter::once(&self.entry_point.function))
+                        .flat_map(|function| function.expressions.iter()),
+                )
+                .any(|(_, expr)| {
+                    matches!(
+                        *expr,
+                        crate::Expression::Literal(
+                            crate::Literal::I64(_) | crate::Literal::U64(_)
+                        )
+                    )
+                });
+            if uses_int64 {
+                if !self.options.version.supports_shader_int64() {
+                    return Err(Error::VersionNotSupported);
+                }
+                self.required_extensions.insert("GL_ARB_gpu_shader_int64");
+            }
+        } else if self.module.types.iter().any(|(_, ty)| {
+            matches!(
+                ty.inner,
+                TypeInner::Scalar(crate::Scalar { kind: crate::ScalarKind::Sint | crate::ScalarKind::Uint, width: 8 })
+                    | TypeInner::Vector { scalar: crate::Scalar { kind: crate::ScalarKind::Sint | crate::ScalarKind::Uint, width: 8 }, .. }
+            )
+        }) {
+            // Without the flag there's no extension to back `int64_t`/`i64vec*`,
+            // so reject 64-bit integer types here, as the literal writer does
+            // for 64-bit literals.
+            return Err(Error::Custom("GLSL has no 64-bit integer type".into()));
+        }
+
+        // 16-bit float literals/types need
+        // `GL_EXT_shader_explicit_arithmetic_types_float16`, opted into via
+        // `WriterFlags::SHADER_FLOAT16` for the same reason as `SHADER_INT64`.
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::SHADER_FLOAT16)
+        {
+            // `write_expr`'s `BinaryOperator`/`Math`/`Select` arms don't
+            // special-case scalar width; they lower straight to GLSL's
+            // generically-typed operators and built-ins, which resolve to
+            // genuine `float16_t`/`f16vec` arithmetic as soon as an operand
+            // has that type. So unlike `F16` literals (which always need
+            // scanning for directly), an f16-typed module can use the
+            // extension purely through typed values (e.g. a function
+            // argument) with no `F16` literal anywhere; check the type arena
+            // too, not just expressions, to catch that case.
+            let uses_float16 = self.module.types.iter().any(|(_, ty)| {
+                matches!(
+                    ty.inner,
+                    TypeInner::Scalar(crate::Scalar { kind: crate::ScalarKind::Float, width: 2 })
+                        | TypeInner::Vector { scalar: crate::Scalar { kind: crate::ScalarKind::Float, width: 2 }, .. }
+                        | TypeInner::Matrix { scalar: crate::Scalar { kind: crate::ScalarKind::Float, width: 2 }, .. }
+                )
+            }) || self
+                .module
+                .global_expressions
+                .iter()
+                .chain(
+                    self.module
+                        .functions
+                        .iter()
+                        .map(|(_, function)| function)
+                        .chain(core::iter::once(&self.entry_point.function))
+                        .flat_map(|function| function.expressions.iter()),
+                )
+                .any(|(_, expr)| {
+                    matches!(
+                        *expr,
+                        crate::Expression::Literal(crate::Literal::F16(_))
+                    )
+                });
+            if uses_float16 {
+                if !self.options.version.supports_shader_float16() {
+                    return Err(Error::VersionNotSupported);
+                }
+                self.required_extensions
+                    .insert("GL_EXT_shader_explicit_arithmetic_types_float16");
+            }
+        }
+
+        // `double`/`dvec*`/`dmat*` need `GL_ARB_gpu_shader_fp64` on profiles
+        // older than GLSL 4.00 and have no ES equivalent at all. Unlike
+        // `SHADER_INT64`/`SHADER_FLOAT16` this doesn't need a `WriterFlags`
+        // opt-in: there's no host-capability ambiguity to resolve, just a
+        // version floor, so it's always checked.
+        let uses_fp64 = self.module.types.iter().any(|(_, ty)| {
+            matches!(
+                ty.inner,
+                TypeInner::Scalar(crate::Scalar { kind: crate::ScalarKind::Float, width: 8 })
+                    | TypeInner::Vector { scalar: crate::Scalar { kind: crate::ScalarKind::Float, width: 8 }, .. }
+                    | TypeInner::Matrix { scalar: crate::Scalar { kind: crate::ScalarKind::Float, width: 8 }, .. }
+            )
+        }) || self
+            .module
+            .global_expressions
+            .iter()
+            .chain(
+                self.module
+                    .functions
+                    .iter()
+                    .map(|(_, function)| function)
+                    .chain(core::iter::once(&self.entry_point.function))
+                    .flat_map(|function| function.expressions.iter()),
+            )
+            .any(|(_, expr)| matches!(*expr, crate::Expression::Literal(crate::Literal::F64(_))));
+        if uses_fp64 {
+            if !self.options.version.supports_fp64() {
+                // No amount of emulation closes this gap: representing
+                // `double` as a compensated pair of `float`s would need a
+                // distinct scalar width threaded through every type,
+                // function signature and struct layout this backend
+                // writes, not just the arithmetic operators — a module-wide
+                // lowering pass upstream of this backend, not something
+                // `write_expr` can paper over expression-by-expression. So
+                // this reports the gap instead of emitting a shader that
+                // references a type the target doesn't have.
+                return Err(Error::Custom(
+                    "64-bit floats require GLSL >= 1.50; this target has no \
+                     software fallback for `double`"
+                        .to_owned(),
+                ));
+            }
+            if self.options.version < Version::Desktop(400) {
+                self.required_extensions.insert("GL_ARB_gpu_shader_fp64");
+            }
+        }
+
+        // `GL_EXT_shader_image_int64` is required whenever the module
+        // declares an `R64Uint` storage image, since that's the only format
+        // 64-bit `imageAtomic*` calls type-check against. (`StorageFormat`
+        // in this tree has no `R64Sint` variant to mirror it with a signed
+        // `r64i` image — only the unsigned format exists here.) There's no
+        // `features.rs` capability to route this through in this tree (`mod
+        // features` is declared but its source isn't part of this
+        // checkout), so it's gated directly off the format, the same way
+        // `uses_fp64` above is gated off the type arena.
+        let uses_64bit_image = self.module.global_variables.iter().any(|(_, var)| {
+            matches!(
+                self.module.types[var.ty].inner,
+                TypeInner::Image {
+                    class: crate::ImageClass::Storage {
+                        format: crate::StorageFormat::R64Uint,
+                        ..
+                    },
+                    ..
+                }
+            )
+        });
+        if uses_64bit_image {
+            if !self.options.version.supports_shader_image_int64() {
+                return Err(Error::Custom(
+                    "64-bit storage image atomics require a target with \
+                     GL_EXT_shader_image_int64 support"
+                        .to_owned(),
+                ));
+            }
+            self.required_extensions
+                .insert("GL_EXT_shader_image_int64");
+        }
+
+        Ok(())
+    }
+
     /// Writes the [`Module`](crate::Module) as glsl to the output
     ///
     /// # Notes
@@ -724,12 +1427,44 @@ impl<'a, W: Write> Writer<'a, W> {
         // preprocessor not the processor ¯\_(ツ)_/¯
         self.features.write(self.options, &mut self.out)?;
 
-        // glsl es requires a precision to be specified for floats and ints
-        // TODO: Should this be user configurable?
+        // Flush the extensions required by constructs discovered ahead of time
+        // in `collect_required_extensions` (dual-source blend, `gl_ClipDistance`,
+        // 8-bit packed dot/pack functions, subgroup operations, ray queries,
+        // 64-bit integers, 16-bit floats, `double`, 64-bit storage images),
+        // for the same reason as above.
+        for extension in self.required_extensions.iter() {
+            writeln!(self.out, "#extension {extension} : require")?;
+        }
+
+        // Request `GL_OVR_multiview2` up front, alongside the other extensions,
+        // since some older compilers require extensions to appear before use.
+        // WebGL needs no explicit request here: it gets multiview implicitly
+        // through the WebXR contract.
+        let multiview_requires_extension = self.entry_point.stage == ShaderStage::Vertex
+            && self.multiview.is_some()
+            && !self.options.version.is_webgl();
+        if multiview_requires_extension {
+            if self.options.version.supports_multiview() {
+                writeln!(self.out, "#extension GL_OVR_multiview2 : require")?;
+            } else {
+                return Err(Error::VersionNotSupported);
+            }
+        }
+
+        // glsl es requires a precision to be specified for floats and ints;
+        // on core profiles precision qualifiers are a no-op, so skip them
         if es {
             writeln!(self.out)?;
-            writeln!(self.out, "precision highp float;")?;
-            writeln!(self.out, "precision highp int;")?;
+            writeln!(
+                self.out,
+                "precision {} float;",
+                self.options.precision.float.as_str()
+            )?;
+            writeln!(
+                self.out,
+                "precision {} int;",
+                self.options.precision.int.as_str()
+            )?;
             writeln!(self.out)?;
         }
 
@@ -780,7 +1515,7 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
-        if self.entry_point.stage == ShaderStage::Vertex && self.options.version.is_webgl() {
+        if self.entry_point.stage == ShaderStage::Vertex {
             if let Some(multiview) = self.multiview.as_ref() {
                 writeln!(self.out, "layout(num_views = {multiview}) in;")?;
                 writeln!(self.out)?;
@@ -929,7 +1664,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     // Gether the location if needed
                     let layout_binding = if self.options.version.supports_explicit_locations() {
                         let br = global.binding.as_ref().unwrap();
-                        self.options.binding_map.get(br).cloned()
+                        self.binding_map.get(br).cloned()
                     } else {
                         None
                     };
@@ -938,7 +1673,11 @@ impl<'a, W: Write> Writer<'a, W> {
                     if layout_binding.is_some() || storage_format_access.is_some() {
                         write!(self.out, "layout(")?;
                         if let Some(binding) = layout_binding {
-                            write!(self.out, "binding = {binding}")?;
+                            if self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                                write!(self.out, "set = {}, binding = {}", binding.set, binding.binding)?;
+                            } else {
+                                write!(self.out, "binding = {binding}")?;
+                            }
                         }
                         if let Some((format, _)) = storage_format_access {
                             let format_str = glsl_storage_format(format)?;
@@ -963,7 +1702,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     //
                     // This is way we need the leading space because `write_image_type` doesn't add
                     // any spaces at the beginning or end
-                    self.write_image_type(dim, arrayed, class)?;
+                    self.write_image_type(dim, arrayed, class, Some(handle))?;
 
                     // Finally write the name and end the global with a `;`
                     // The leading space is important
@@ -973,8 +1712,64 @@ impl<'a, W: Write> Writer<'a, W> {
 
                     self.reflection_names_globals.insert(handle, global_name);
                 }
-                // glsl has no concept of samplers so we just ignore it
-                TypeInner::Sampler { .. } => continue,
+                // Pre-Vulkan GLSL has no concept of samplers so we just ignore it. In
+                // `VULKAN_GLSL` mode samplers are real bindings: the texture they're
+                // combined with at use sites is written as a bare `textureND` above.
+                TypeInner::Sampler { .. } => {
+                    if !self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                        continue;
+                    }
+
+                    let layout_binding = if self.options.version.supports_explicit_locations() {
+                        let br = global.binding.as_ref().unwrap();
+                        self.binding_map.get(br).cloned()
+                    } else {
+                        None
+                    };
+
+                    if let Some(binding) = layout_binding {
+                        writeln!(
+                            self.out,
+                            "layout(set = {}, binding = {}) uniform sampler {};",
+                            binding.set,
+                            binding.binding,
+                            self.get_global_name(handle, global)
+                        )?;
+                    } else {
+                        writeln!(self.out, "uniform sampler {};", self.get_global_name(handle, global))?;
+                    }
+                    writeln!(self.out)?;
+                }
+                // Acceleration structures only make sense as Vulkan GLSL descriptor
+                // bindings; there's no non-Vulkan GL equivalent to bind one to.
+                TypeInner::AccelerationStructure { .. } => {
+                    if !self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                        return Err(Error::Custom(
+                            "acceleration structures are only supported when targeting Vulkan GLSL"
+                                .to_owned(),
+                        ));
+                    }
+
+                    let br = global.binding.as_ref().unwrap();
+                    let layout_binding = self.binding_map.get(br).cloned();
+
+                    if let Some(binding) = layout_binding {
+                        writeln!(
+                            self.out,
+                            "layout(set = {}, binding = {}) uniform accelerationStructureEXT {};",
+                            binding.set,
+                            binding.binding,
+                            self.get_global_name(handle, global)
+                        )?;
+                    } else {
+                        writeln!(
+                            self.out,
+                            "uniform accelerationStructureEXT {};",
+                            self.get_global_name(handle, global)
+                        )?;
+                    }
+                    writeln!(self.out)?;
+                }
                 // All other globals are written by `write_global`
                 _ => {
                     self.write_global(handle, global)?;
@@ -1105,6 +1900,9 @@ impl<'a, W: Write> Writer<'a, W> {
             // Here we only write the size of the array i.e. `[size]`
             // Base `type` and `name` should be written outside
             TypeInner::Array { base, size, .. } => self.write_array_size(base, size)?,
+            // A `rayQueryEXT` is opaque state local to the function it's declared
+            // in; GLSL has no other way to name it.
+            TypeInner::RayQuery { .. } => write!(self.out, "rayQueryEXT")?,
             // Write all variants instead of `_` so that if new variants are added a
             // no exhaustiveness error is thrown
             TypeInner::Pointer { .. }
@@ -1112,7 +1910,6 @@ impl<'a, W: Write> Writer<'a, W> {
             | TypeInner::Image { .. }
             | TypeInner::Sampler { .. }
             | TypeInner::AccelerationStructure { .. }
-            | TypeInner::RayQuery { .. }
             | TypeInner::BindingArray { .. } => {
                 return Err(Error::Custom(format!("Unable to write type {inner:?}")))
             }
@@ -1151,6 +1948,7 @@ impl<'a, W: Write> Writer<'a, W> {
         dim: crate::ImageDimension,
         arrayed: bool,
         class: crate::ImageClass,
+        global_handle: Option<Handle<crate::GlobalVariable>>,
     ) -> BackendResult {
         // glsl images consist of four parts the scalar prefix, the image "type", the dimensions
         // and modifiers
@@ -1170,16 +1968,52 @@ impl<'a, W: Write> Writer<'a, W> {
             kind: crate::ScalarKind::Float,
             width: 4,
         };
+        // In `VULKAN_GLSL` mode sampled and depth images are declared as bare
+        // `textureND` globals, with the combined `samplerND[Shadow]` only
+        // reconstructed at use sites (see `write_combined_sampler_type`), so
+        // that the sampler can be bound as a separate Vulkan descriptor.
+        let vulkan_glsl = self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL);
         let (base, scalar, ms, comparison) = match class {
-            Ic::Sampled { kind, multi: true } => ("sampler", S { kind, width: 4 }, "MS", ""),
-            Ic::Sampled { kind, multi: false } => ("sampler", S { kind, width: 4 }, "", ""),
-            Ic::Depth { multi: true } => ("sampler", float, "MS", ""),
-            Ic::Depth { multi: false } => ("sampler", float, "", "Shadow"),
+            Ic::Sampled { kind, multi: true } => (
+                if vulkan_glsl { "texture" } else { "sampler" },
+                S { kind, width: 4 },
+                "MS",
+                "",
+            ),
+            Ic::Sampled { kind, multi: false } => (
+                if vulkan_glsl { "texture" } else { "sampler" },
+                S { kind, width: 4 },
+                "",
+                "",
+            ),
+            Ic::Depth { multi: true } => (
+                if vulkan_glsl { "texture" } else { "sampler" },
+                float,
+                "MS",
+                "",
+            ),
+            Ic::Depth { multi: false } => (
+                if vulkan_glsl { "texture" } else { "sampler" },
+                float,
+                "",
+                if vulkan_glsl { "" } else { "Shadow" },
+            ),
             Ic::Storage { format, .. } => ("image", format.into(), "", ""),
         };
 
+        // Per-global override takes precedence over `Options::precision`'s
+        // float default; storage images, samplers, etc. all key off the
+        // float scalar since GLSL has no separate int image/sampler precision.
         let precision = if self.options.version.is_es() {
-            "highp "
+            let precision = global_handle
+                .and_then(|handle| self.options.precision_overrides.get(&handle))
+                .copied()
+                .unwrap_or(self.options.precision.float);
+            match precision {
+                Precision::High => "highp ",
+                Precision::Medium => "mediump ",
+                Precision::Low => "lowp ",
+            }
         } else {
             ""
         };
@@ -1199,6 +2033,49 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method to write the combined `samplerND[Shadow]` type used to
+    /// reconstruct separate-texture-and-sampler access (`sampler2D(tex, samp)`)
+    /// at a use site when [`WriterFlags::VULKAN_GLSL`] is set.
+    ///
+    /// # Notes
+    /// Adds no leading or trailing whitespace
+    fn write_combined_sampler_type(
+        &mut self,
+        dim: crate::ImageDimension,
+        arrayed: bool,
+        class: crate::ImageClass,
+    ) -> BackendResult {
+        use crate::ImageClass as Ic;
+        use crate::Scalar as S;
+        let float = S {
+            kind: crate::ScalarKind::Float,
+            width: 4,
+        };
+        let (scalar, ms, comparison) = match class {
+            Ic::Sampled { kind, multi: true } => (S { kind, width: 4 }, "MS", ""),
+            Ic::Sampled { kind, multi: false } => (S { kind, width: 4 }, "", ""),
+            Ic::Depth { multi: true } => (float, "MS", ""),
+            Ic::Depth { multi: false } => (float, "", "Shadow"),
+            Ic::Storage { .. } => {
+                return Err(Error::Custom(
+                    "storage images have no separate sampler".to_string(),
+                ))
+            }
+        };
+
+        write!(
+            self.out,
+            "{}sampler{}{}{}{}",
+            glsl_scalar(scalar)?.prefix,
+            glsl_dimension(dim),
+            ms,
+            if arrayed { "Array" } else { "" },
+            comparison
+        )?;
+
+        Ok(())
+    }
+
     /// Helper method used by [Self::write_global] to write just the layout part of
     /// a non image/sampler global variable, if applicable.
     ///
@@ -1206,6 +2083,25 @@ impl<'a, W: Write> Writer<'a, W> {
     ///
     /// Adds trailing whitespace if any layout qualifier is written
     fn write_global_layout(&mut self, global: &crate::GlobalVariable) -> BackendResult {
+        // Emulated push constants are bound like a regular uniform block, but
+        // at a binding slot configured directly via `Options` rather than one
+        // resolved through `Self::binding_map` (push constants have no Naga
+        // `ResourceBinding` to look up).
+        if let crate::AddressSpace::PushConstant = global.space {
+            if let Some(binding) = self.options.push_constant_binding {
+                if self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                    write!(
+                        self.out,
+                        "layout(std140, set = {}, binding = {}) ",
+                        binding.set, binding.binding
+                    )?;
+                } else {
+                    write!(self.out, "layout(std140, binding = {}) ", binding.binding)?;
+                }
+                return Ok(());
+            }
+        }
+
         // Determine which (if any) explicit memory layout to use, and whether we support it
         let layout = match global.space {
             crate::AddressSpace::Uniform => {
@@ -1233,7 +2129,7 @@ impl<'a, W: Write> Writer<'a, W> {
         // if we have it
         if self.options.version.supports_explicit_locations() {
             if let Some(ref br) = global.binding {
-                match self.options.binding_map.get(br) {
+                match self.binding_map.get(br) {
                     Some(binding) => {
                         write!(self.out, "layout(")?;
 
@@ -1241,7 +2137,11 @@ impl<'a, W: Write> Writer<'a, W> {
                             write!(self.out, "{}, ", layout)?;
                         }
 
-                        write!(self.out, "binding = {binding}) ")?;
+                        if self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                            write!(self.out, "set = {}, binding = {}) ", binding.set, binding.binding)?;
+                        } else {
+                            write!(self.out, "binding = {binding}) ")?;
+                        }
 
                         return Ok(());
                     }
@@ -1291,7 +2191,11 @@ impl<'a, W: Write> Writer<'a, W> {
                 self.write_simple_global(handle, global)?;
             }
             crate::AddressSpace::PushConstant => {
-                self.write_simple_global(handle, global)?;
+                if self.options.push_constant_binding.is_some() {
+                    self.write_push_constant_block(handle, global)?;
+                } else {
+                    self.write_simple_global(handle, global)?;
+                }
             }
             crate::AddressSpace::Uniform => {
                 self.write_interface_block(handle, global)?;
@@ -1314,6 +2218,14 @@ impl<'a, W: Write> Writer<'a, W> {
         handle: Handle<crate::GlobalVariable>,
         global: &crate::GlobalVariable,
     ) -> BackendResult {
+        // An explicit per-global precision qualifier, if one was requested in
+        // `Options::precision_overrides`, takes precedence over the default
+        // `precision` statement emitted at the top of the file.
+        if self.options.version.is_es() {
+            if let Some(precision) = self.options.precision_overrides.get(&handle) {
+                write!(self.out, "{} ", precision.as_str())?;
+            }
+        }
         self.write_type(global.ty)?;
         write!(self.out, " ")?;
         self.write_global_name(handle, global)?;
@@ -1352,7 +2264,7 @@ impl<'a, W: Write> Writer<'a, W> {
         handle: Handle<crate::GlobalVariable>,
         global: &crate::GlobalVariable,
     ) -> BackendResult {
-        // Write the block name, it's just the struct name appended with `_block_ID`
+        // The block name is just the struct name appended with `_block_ID`
         let ty_name = &self.names[&NameKey::Type(global.ty)];
         let block_name = format!(
             "{}_block_{}{:?}",
@@ -1361,18 +2273,52 @@ impl<'a, W: Write> Writer<'a, W> {
             self.block_id.generate(),
             self.entry_point.stage,
         );
-        write!(self.out, "{block_name} ")?;
-        self.reflection_names_globals.insert(handle, block_name);
+        self.write_interface_block_body(handle, global, block_name)
+    }
 
-        match self.module.types[global.ty].inner {
-            TypeInner::Struct { ref members, .. }
-                if self.module.types[members.last().unwrap().ty]
+    /// Helper method used to write a push-constant global emulated as a
+    /// `std140` uniform block (see [`Options::push_constant_binding`]),
+    /// instead of the plain-global lowering [`Self::write_simple_global`]
+    /// produces.
+    ///
+    /// Unlike [`Self::write_interface_block`], the block is given the stable
+    /// name `_push_constant_binding_STAGE` (matching the reflection name the
+    /// plain-global path already uses) rather than one keyed off an
+    /// `IdGenerator` counter, since the host needs to find this block by name
+    /// across recompiles to bind it once and update it with `glBufferSubData`.
+    fn write_push_constant_block(
+        &mut self,
+        handle: Handle<crate::GlobalVariable>,
+        global: &crate::GlobalVariable,
+    ) -> BackendResult {
+        let block_name = format!("_push_constant_binding_{}", self.entry_point.stage.to_str());
+        self.write_interface_block_body(handle, global, block_name)
+    }
+
+    /// Shared body-writer for [`Self::write_interface_block`] and
+    /// [`Self::write_push_constant_block`]: writes `block_name { members };`,
+    /// lifting a trailing dynamically-sized array's members to the top level
+    /// of the block where needed, and records `block_name` as the global's
+    /// reflection name.
+    fn write_interface_block_body(
+        &mut self,
+        handle: Handle<crate::GlobalVariable>,
+        global: &crate::GlobalVariable,
+        block_name: String,
+    ) -> BackendResult {
+        write!(self.out, "{block_name} ")?;
+        self.reflection_names_globals.insert(handle, block_name);
+
+        match self.module.types[global.ty].inner {
+            TypeInner::Struct { ref members, .. }
+                if self.module.types[members.last().unwrap().ty]
                     .inner
                     .is_dynamically_sized(&self.module.types) =>
             {
                 // Structs with dynamically sized arrays must have their
                 // members lifted up as members of the interface block. GLSL
                 // can't write such struct types anyway.
+                self.lifted_interface_blocks.insert(handle);
                 self.write_struct_body(global.ty, members)?;
                 write!(self.out, " ")?;
                 self.write_global_name(handle, global)?;
@@ -1418,6 +2364,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 arg,
                 arg1,
                 arg2,
+                arg3,
                 ..
             } = *expr
             {
@@ -1447,44 +2394,67 @@ impl<'a, W: Write> Writer<'a, W> {
                     | crate::MathFunction::QuantizeToF16 => {
                         self.need_bake_expressions.insert(arg);
                     }
-                    /* crate::MathFunction::Pack4x8unorm | */
-                    crate::MathFunction::Unpack4x8snorm
+                    crate::MathFunction::Pack4x8snorm | crate::MathFunction::Unpack4x8snorm
                         if !self.options.version.supports_pack_unpack_4x8() =>
                     {
                         // We have a fallback if the platform doesn't natively support these
                         self.need_bake_expressions.insert(arg);
                     }
-                    /* crate::MathFunction::Pack4x8unorm | */
-                    crate::MathFunction::Unpack4x8unorm
+                    crate::MathFunction::Pack4x8unorm | crate::MathFunction::Unpack4x8unorm
                         if !self.options.version.supports_pack_unpack_4x8() =>
                     {
                         self.need_bake_expressions.insert(arg);
                     }
-                    /* crate::MathFunction::Pack2x16snorm |  */
-                    crate::MathFunction::Unpack2x16snorm
+                    crate::MathFunction::Pack2x16snorm | crate::MathFunction::Unpack2x16snorm
                         if !self.options.version.supports_pack_unpack_snorm_2x16() =>
                     {
                         self.need_bake_expressions.insert(arg);
                     }
-                    /* crate::MathFunction::Pack2x16unorm | */
-                    crate::MathFunction::Unpack2x16unorm
+                    crate::MathFunction::Pack2x16unorm | crate::MathFunction::Unpack2x16unorm
                         if !self.options.version.supports_pack_unpack_unorm_2x16() =>
                     {
                         self.need_bake_expressions.insert(arg);
                     }
+                    crate::MathFunction::Pack2x16float | crate::MathFunction::Unpack2x16float
+                        if !self.options.version.supports_pack_unpack_half_2x16() =>
+                    {
+                        self.need_bake_expressions.insert(arg);
+                    }
                     crate::MathFunction::ExtractBits => {
-                        // Only argument 1 is re-used.
+                        // Argument 1 (offset) is always re-used; the
+                        // shift-and-mask fallback below (for profiles
+                        // lacking `bitfieldExtract`) re-uses argument 2
+                        // (count) as well.
                         self.need_bake_expressions.insert(arg1.unwrap());
+                        if !self.options.version.supports_integer_functions() {
+                            self.need_bake_expressions.insert(arg2.unwrap());
+                        }
                     }
                     crate::MathFunction::InsertBits => {
-                        // Only argument 2 is re-used.
+                        // Argument 2 (offset) is always re-used; the
+                        // shift-and-mask fallback below (for profiles
+                        // lacking `bitfieldInsert`) re-uses argument 3
+                        // (count) as well.
                         self.need_bake_expressions.insert(arg2.unwrap());
+                        if !self.options.version.supports_integer_functions() {
+                            self.need_bake_expressions.insert(arg3.unwrap());
+                        }
                     }
                     crate::MathFunction::CountLeadingZeros => {
                         if let Some(crate::ScalarKind::Sint) = inner.scalar_kind() {
                             self.need_bake_expressions.insert(arg);
                         }
                     }
+                    crate::MathFunction::CountOneBits
+                    | crate::MathFunction::ReverseBits
+                    | crate::MathFunction::CountTrailingZeros
+                        if !self.options.version.supports_integer_functions() =>
+                    {
+                        // The polyfills used when `bitCount`/`bitfieldReverse`/
+                        // `findLSB` aren't available all re-use their operand
+                        // more than once.
+                        self.need_bake_expressions.insert(arg);
+                    }
                     _ => {}
                 }
             }
@@ -1559,6 +2529,22 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Returns how many consecutive GLSL varying locations `ty` occupies.
+    ///
+    /// A matrix consumes one location per column, and an array of known
+    /// length repeats its base type's span for each element, mirroring the
+    /// location-assignment rules `valid::interface` enforces on the IR side.
+    fn varying_location_span(&self, ty: Handle<crate::Type>) -> Result<u32, Error> {
+        Ok(match self.module.types[ty].inner {
+            TypeInner::Matrix { columns, .. } => columns as u32,
+            TypeInner::Array { base, size, .. } => match size.resolve(self.module.to_ctx())? {
+                proc::IndexableLength::Known(count) => count * self.varying_location_span(base)?,
+                proc::IndexableLength::Dynamic => 1,
+            },
+            _ => 1,
+        })
+    }
+
     /// Write a GLSL global that will carry a Naga entry point's argument or return value.
     ///
     /// A Naga entry point's arguments and return value are rendered in GLSL as
@@ -1661,6 +2647,27 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         };
 
+        // Check that this varying, together with everything written so far, still
+        // fits within the minimum number of locations guaranteed by every GLSL
+        // version we support. This can't guarantee the vertex and fragment stages
+        // agree on layout when locations are assigned implicitly, but it does turn
+        // obvious over-allocation into a hard error instead of a silently mislinked
+        // program.
+        let span = self.varying_location_span(ty)?;
+        let highest_location = location + span - 1;
+        // Vertex inputs and fragment outputs aren't varyings and have their
+        // own limits, so only the vertex-to-fragment interface is checked.
+        let is_varying = matches!(
+            (self.entry_point.stage, output),
+            (ShaderStage::Vertex, true) | (ShaderStage::Fragment, false)
+        );
+        if is_varying && highest_location >= MIN_GUARANTEED_VARYING_LOCATIONS {
+            return Err(Error::Custom(format!(
+                "varying at location {location} (spanning {span} location(s)) exceeds the \
+                 minimum guaranteed varying location limit of {MIN_GUARANTEED_VARYING_LOCATIONS}"
+            )));
+        }
+
         // Write the interpolation modifier if needed
         //
         // We ignore all interpolation and auxiliary modifiers that aren't used in fragment
@@ -1835,7 +2842,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     //
                     // This is way we need the leading space because `write_image_type` doesn't add
                     // any spaces at the beginning or end
-                    this.write_image_type(dim, arrayed, class)?;
+                    this.write_image_type(dim, arrayed, class, None)?;
                 }
                 TypeInner::Pointer { base, .. } => {
                     // write parameter qualifiers
@@ -2189,6 +3196,18 @@ impl<'a, W: Write> Writer<'a, W> {
                         }
                     }
 
+                    // `clamp_to_edge` sampling reads `image` twice (once per
+                    // `textureSize` call in the coordinate inset); hoist it
+                    // into a local first so it's evaluated exactly once.
+                    if let crate::Expression::ImageSample {
+                        image,
+                        clamp_to_edge: true,
+                        ..
+                    } = ctx.expressions[handle]
+                    {
+                        self.cache_expr_for_reuse(ctx, image, level)?;
+                    }
+
                     if let Some(name) = expr_name {
                         write!(self.out, "{level}")?;
                         self.write_named_expr(handle, name, handle, ctx)?;
@@ -2566,10 +3585,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 coordinate,
                 array_index,
                 value,
-            } => {
-                write!(self.out, "{level}")?;
-                self.write_image_store(ctx, image, coordinate, array_index, value)?
-            }
+            } => self.write_image_store(ctx, image, coordinate, array_index, value, level)?,
             // A `Call` is written `name(arguments)` where `arguments` is a comma separated expressions list
             Statement::Call {
                 function,
@@ -2616,6 +3632,10 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::AtomicFunction::Exchange {
                         compare: Some(compare_expr),
                     } => {
+                        if !self.options.version.supports_atomic_compare_exchange() {
+                            return Err(Error::VersionNotSupported);
+                        }
+
                         let result_handle = result.expect("CompareExchange must have a result");
                         let res_name = Baked(result_handle).to_string();
                         self.write_type(ctx.info[result_handle].ty.handle().unwrap())?;
@@ -2663,11 +3683,16 @@ impl<'a, W: Write> Writer<'a, W> {
                 array_index,
                 fun,
                 value,
-            } => {
-                write!(self.out, "{level}")?;
-                self.write_image_atomic(ctx, image, coordinate, array_index, fun, value)?
+            } => self.write_image_atomic(ctx, image, coordinate, array_index, fun, value, level)?,
+            Statement::RayQuery { query, ref fun } => {
+                self.write_ray_query_function(query, fun, ctx, level)?;
             }
-            Statement::RayQuery { .. } => unreachable!(),
+            // Note: the DOT graph backend (`back::dot`) needs matching
+            // `StatementGraph` entries for `SubgroupBallot`,
+            // `SubgroupCollectiveOperation`, and `SubgroupGather` so that
+            // dumping a module using these doesn't produce an incomplete
+            // dependency graph; that backend isn't present in this checkout
+            // to update alongside this one.
             Statement::SubgroupBallot { result, predicate } => {
                 write!(self.out, "{level}")?;
                 let res_name = Baked(result).to_string();
@@ -2808,6 +3833,65 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Lower a [`crate::Statement::RayQuery`] to the matching `rayQueryEXT`
+    /// built-in call under `GL_EXT_ray_query`.
+    ///
+    /// Relies on the WGSL `RayDesc` built-in's field layout (`flags`,
+    /// `cull_mask`, `tmin`, `tmax`, `origin`, `dir`) to destructure
+    /// `descriptor` into `rayQueryInitializeEXT`'s separate arguments, since
+    /// GLSL has no equivalent aggregate parameter for it.
+    fn write_ray_query_function(
+        &mut self,
+        query: Handle<crate::Expression>,
+        fun: &crate::RayQueryFunction,
+        ctx: &back::FunctionCtx,
+        level: back::Level,
+    ) -> BackendResult {
+        if !self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL)
+            || self.options.version.is_es()
+        {
+            return Err(Error::Custom(
+                "ray queries are only supported when targeting Vulkan GLSL".to_owned(),
+            ));
+        }
+
+        use crate::RayQueryFunction as Rqf;
+        match *fun {
+            Rqf::Initialize {
+                acceleration_structure,
+                descriptor,
+            } => {
+                write!(self.out, "{level}rayQueryInitializeEXT(")?;
+                self.write_expr(query, ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(acceleration_structure, ctx)?;
+                for field in ["flags", "cull_mask", "origin", "tmin", "dir", "tmax"] {
+                    write!(self.out, ", ")?;
+                    self.write_expr(descriptor, ctx)?;
+                    write!(self.out, ".{field}")?;
+                }
+                writeln!(self.out, ");")?;
+            }
+            Rqf::Proceed { result } => {
+                write!(self.out, "{level}")?;
+                let res_name = Baked(result).to_string();
+                let res_ty = ctx.info[result].ty.inner_with(&self.module.types);
+                self.write_value_type(res_ty)?;
+                write!(self.out, " {res_name} = rayQueryProceedEXT(")?;
+                self.named_expressions.insert(result, res_name);
+                self.write_expr(query, ctx)?;
+                writeln!(self.out, ");")?;
+            }
+            Rqf::Terminate => {
+                write!(self.out, "{level}rayQueryTerminateEXT(")?;
+                self.write_expr(query, ctx)?;
+                writeln!(self.out, ");")?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write a const expression.
     ///
     /// Write `expr`, a handle to an [`Expression`] in the current [`Module`]'s
@@ -2869,6 +3953,16 @@ impl<'a, W: Write> Writer<'a, W> {
                     // decimal part even it's zero which is needed for a valid glsl float constant
                     crate::Literal::F64(value) => write!(self.out, "{value:?}LF")?,
                     crate::Literal::F32(value) => write!(self.out, "{value:?}")?,
+                    // Only reachable with `GL_EXT_shader_explicit_arithmetic_types_float16`
+                    // support, since `collect_required_extensions` rejects the module otherwise.
+                    crate::Literal::F16(value)
+                        if self
+                            .options
+                            .writer_flags
+                            .contains(WriterFlags::SHADER_FLOAT16) =>
+                    {
+                        write!(self.out, "{value:?}hf")?;
+                    }
                     crate::Literal::F16(_) => {
                         return Err(Error::Custom("GLSL has no 16-bit float type".into()));
                     }
@@ -2879,10 +3973,25 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::Literal::U32(value) => write!(self.out, "{value}u")?,
                     crate::Literal::I32(value) => write!(self.out, "{value}")?,
                     crate::Literal::Bool(value) => write!(self.out, "{value}")?,
-                    crate::Literal::I64(_) => {
-                        return Err(Error::Custom("GLSL has no 64-bit integer type".into()));
+                    // Only reachable with `GL_ARB_gpu_shader_int64` support, since
+                    // `collect_required_extensions` rejects the module otherwise.
+                    crate::Literal::I64(value)
+                        if self
+                            .options
+                            .writer_flags
+                            .contains(WriterFlags::SHADER_INT64) =>
+                    {
+                        write!(self.out, "{value}L")?;
                     }
-                    crate::Literal::U64(_) => {
+                    crate::Literal::U64(value)
+                        if self
+                            .options
+                            .writer_flags
+                            .contains(WriterFlags::SHADER_INT64) =>
+                    {
+                        write!(self.out, "{value}UL")?;
+                    }
+                    crate::Literal::I64(_) | crate::Literal::U64(_) => {
                         return Err(Error::Custom("GLSL has no 64-bit integer type".into()));
                     }
                     crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
@@ -3049,14 +4158,14 @@ impl<'a, W: Write> Writer<'a, W> {
             // Furthermore if `depth_ref` is some we need to append it to the coordinate vector
             Expression::ImageSample {
                 image,
-                sampler: _, //TODO?
+                sampler,
                 gather,
                 coordinate,
                 array_index,
                 offset,
                 level,
                 depth_ref,
-                clamp_to_edge: _,
+                clamp_to_edge,
             } => {
                 let (dim, class, arrayed) = match *ctx.resolve_type(image, &self.module.types) {
                     TypeInner::Image {
@@ -3067,6 +4176,14 @@ impl<'a, W: Write> Writer<'a, W> {
                     } => (dim, class, arrayed),
                     _ => unreachable!(),
                 };
+                // Note: a `gather` with a non-`Zero` `SampleLevel`, and an
+                // `offset` on a `Cube` image, are invalid for every backend,
+                // not just GLSL, and ideally would be rejected up front by a
+                // dedicated `ExpressionError` in `valid/expression.rs` so
+                // non-GLSL targets get the same guarantee and the errors
+                // carry source spans. That module isn't present in this
+                // checkout to extend, so these remain backend-level checks
+                // here for now.
                 let mut err = None;
                 if dim == crate::ImageDimension::Cube {
                     if offset.is_some() {
@@ -3110,13 +4227,31 @@ impl<'a, W: Write> Writer<'a, W> {
 
                 write!(self.out, "{fun_name}{offset_name}(")?;
 
-                // Write the image that will be used
-                self.write_expr(image, ctx)?;
+                // Write the image that will be used.
+                //
+                // In `VULKAN_GLSL` mode `image` and `sampler` are separate descriptors,
+                // so reconstruct the combined sampler GLSL expects at the use site.
+                if self.options.writer_flags.contains(WriterFlags::VULKAN_GLSL) {
+                    self.write_combined_sampler_type(dim, arrayed, class)?;
+                    write!(self.out, "(")?;
+                    self.write_expr(image, ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(sampler, ctx)?;
+                    write!(self.out, ")")?;
+                } else {
+                    self.write_expr(image, ctx)?;
+                }
                 // The space here isn't required but it helps with readability
                 write!(self.out, ", ")?;
 
-                // TODO: handle clamp_to_edge
-                // https://github.com/gfx-rs/wgpu/issues/7791
+                // `clamp_to_edge` only occurs with 2D float textures sampled
+                // at level 0 (external/video-texture sampling), and asks us
+                // to replicate `CLAMP_TO_EDGE` addressing even when the bound
+                // sampler doesn't use it; there's no sampler state override
+                // for that in GLSL, so instead inset the normalized
+                // coordinate by half a texel on each edge before sampling.
+                // This is written into the coordinate position below so the
+                // array-index/level/offset logic that follows is unaffected.
 
                 // We need to get the coordinates vector size to later build a vector that's `size + 1`
                 // if `depth_ref` is some, if it isn't a vector we panic as that's not a valid expression
@@ -3129,6 +4264,12 @@ impl<'a, W: Write> Writer<'a, W> {
                 if array_index.is_some() {
                     coord_dim += 1;
                 }
+                // Shadow `textureGather` takes the depth reference as its own
+                // trailing scalar argument (`textureGather(sampler2DShadow,
+                // coord, refZ)`) rather than folded into the coordinate
+                // vector the way ordinary shadow sampling does, so exclude
+                // it here whenever `gather` is set; the `(Some(expr), false)`
+                // branch below appends it separately instead.
                 let merge_depth_ref = depth_ref.is_some() && gather.is_none() && coord_dim < 4;
                 if merge_depth_ref {
                     coord_dim += 1;
@@ -3140,7 +4281,17 @@ impl<'a, W: Write> Writer<'a, W> {
                 if is_vec {
                     write!(self.out, "vec{}(", coord_dim + tex_1d_hack as u8)?;
                 }
-                self.write_expr(coordinate, ctx)?;
+                if clamp_to_edge {
+                    write!(self.out, "clamp(")?;
+                    self.write_expr(coordinate, ctx)?;
+                    write!(self.out, ", vec2(0.5) / vec2(textureSize(")?;
+                    self.write_expr(image, ctx)?;
+                    write!(self.out, ", 0)), vec2(1.0) - vec2(0.5) / vec2(textureSize(")?;
+                    self.write_expr(image, ctx)?;
+                    write!(self.out, ", 0)))")?;
+                } else {
+                    self.write_expr(coordinate, ctx)?;
+                }
                 if tex_1d_hack {
                     write!(self.out, ", 0.0")?;
                 }
@@ -3386,7 +4537,11 @@ impl<'a, W: Write> Writer<'a, W> {
                         | Bo::GreaterEqual
                         | Bo::Equal
                         | Bo::NotEqual => BinaryOperation::VectorCompare,
-                        Bo::Modulo if scalar.kind == Sk::Float => BinaryOperation::Modulo,
+                        Bo::Modulo
+                            if matches!(scalar.kind, Sk::Float | Sk::Sint | Sk::Uint) =>
+                        {
+                            BinaryOperation::Modulo
+                        }
                         Bo::And if scalar.kind == Sk::Bool => {
                             op = crate::BinaryOperator::LogicalAnd;
                             BinaryOperation::VectorComponentWise
@@ -3402,6 +4557,12 @@ impl<'a, W: Write> Writer<'a, W> {
                             Bo::Modulo => BinaryOperation::Modulo,
                             _ => BinaryOperation::Other,
                         },
+                        (Some(Sk::Sint | Sk::Uint), _) | (_, Some(Sk::Sint | Sk::Uint)) => {
+                            match op {
+                                Bo::Modulo => BinaryOperation::Modulo,
+                                _ => BinaryOperation::Other,
+                            }
+                        }
                         (Some(Sk::Bool), Some(Sk::Bool)) => match op {
                             Bo::InclusiveOr => {
                                 op = crate::BinaryOperator::LogicalOr;
@@ -3459,33 +4620,78 @@ impl<'a, W: Write> Writer<'a, W> {
 
                         write!(self.out, ")")?;
                     }
-                    // TODO: handle undefined behavior of BinaryOperator::Modulo
-                    //
-                    // sint:
-                    // if right == 0 return 0
-                    // if left == min(type_of(left)) && right == -1 return 0
-                    // if sign(left) == -1 || sign(right) == -1 return result as defined by WGSL
-                    //
-                    // uint:
-                    // if right == 0 return 0
-                    //
                     // float:
                     // if right == 0 return ? see https://github.com/gpuweb/gpuweb/issues/2798
                     BinaryOperation::Modulo => {
-                        write!(self.out, "(")?;
+                        let scalar = match *left_inner {
+                            Ti::Scalar(scalar) | Ti::Vector { scalar, .. } => scalar,
+                            _ => unreachable!(),
+                        };
 
-                        // write `e1 - e2 * trunc(e1 / e2)`
-                        self.write_expr(left, ctx)?;
-                        write!(self.out, " - ")?;
-                        self.write_expr(right, ctx)?;
-                        write!(self.out, " * ")?;
-                        write!(self.out, "trunc(")?;
-                        self.write_expr(left, ctx)?;
-                        write!(self.out, " / ")?;
-                        self.write_expr(right, ctx)?;
-                        write!(self.out, ")")?;
+                        match scalar.kind {
+                            Sk::Sint | Sk::Uint => {
+                                let (zero, overflow_guard) = match (scalar.kind, scalar.width) {
+                                    (Sk::Uint, 4) => ("0u", None),
+                                    (Sk::Uint, 8) => ("0UL", None),
+                                    (Sk::Sint, 4) => {
+                                        ("0", Some(("(-2147483647 - 1)", "-1")))
+                                    }
+                                    (Sk::Sint, 8) => (
+                                        "0L",
+                                        Some(("(-9223372036854775807L - 1L)", "-1L")),
+                                    ),
+                                    _ => unreachable!(),
+                                };
+
+                                match *left_inner {
+                                    Ti::Vector { size, .. } => {
+                                        self.write_value_type(left_inner)?;
+                                        write!(self.out, "(")?;
+                                        for i in 0..size as usize {
+                                            if i != 0 {
+                                                write!(self.out, ", ")?;
+                                            }
+                                            self.write_int_modulo(
+                                                left,
+                                                right,
+                                                ctx,
+                                                Some(i),
+                                                zero,
+                                                overflow_guard,
+                                            )?;
+                                        }
+                                        write!(self.out, ")")?;
+                                    }
+                                    _ => {
+                                        self.write_int_modulo(
+                                            left,
+                                            right,
+                                            ctx,
+                                            None,
+                                            zero,
+                                            overflow_guard,
+                                        )?;
+                                    }
+                                }
+                            }
+                            Sk::Float => {
+                                write!(self.out, "(")?;
+
+                                // write `e1 - e2 * trunc(e1 / e2)`
+                                self.write_expr(left, ctx)?;
+                                write!(self.out, " - ")?;
+                                self.write_expr(right, ctx)?;
+                                write!(self.out, " * ")?;
+                                write!(self.out, "trunc(")?;
+                                self.write_expr(left, ctx)?;
+                                write!(self.out, " / ")?;
+                                self.write_expr(right, ctx)?;
+                                write!(self.out, ")")?;
 
-                        write!(self.out, ")")?;
+                                write!(self.out, ")")?;
+                            }
+                            _ => unreachable!(),
+                        }
                     }
                     BinaryOperation::Other => {
                         write!(self.out, "(")?;
@@ -3693,18 +4899,47 @@ impl<'a, W: Write> Writer<'a, W> {
                         // with different precedences from applying earlier.
                         write!(self.out, "(")?;
                         for i in 0..4 {
-                            // Since `bitfieldExtract` only sign extends if the value is signed, we
-                            // need to convert the inputs to `int` in case of `Dot4I8Packed`. For
-                            // `Dot4U8Packed`, the code below only introduces parenthesis around
-                            // each factor, which aren't strictly needed because both operands are
-                            // baked, but which don't hurt either.
-                            write!(self.out, "bitfieldExtract({}(", conversion)?;
-                            self.write_expr(arg, ctx)?;
-                            write!(self.out, "), {}, 8)", i * 8)?;
+                            if self.options.version.supports_integer_functions() {
+                                // Since `bitfieldExtract` only sign extends if the value is signed, we
+                                // need to convert the inputs to `int` in case of `Dot4I8Packed`. For
+                                // `Dot4U8Packed`, the code below only introduces parenthesis around
+                                // each factor, which aren't strictly needed because both operands are
+                                // baked, but which don't hurt either.
+                                write!(self.out, "bitfieldExtract({}(", conversion)?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, "), {}, 8)", i * 8)?;
 
-                            write!(self.out, " * bitfieldExtract({}(", conversion)?;
-                            self.write_expr(arg1, ctx)?;
-                            write!(self.out, "), {}, 8)", i * 8)?;
+                                write!(self.out, " * bitfieldExtract({}(", conversion)?;
+                                self.write_expr(arg1, ctx)?;
+                                write!(self.out, "), {}, 8)", i * 8)?;
+                            } else {
+                                // `bitfieldExtract` is unavailable here, but unlike
+                                // `ExtractBits` the offset is a compile-time constant
+                                // (the loop index), so the shift-and-mask equivalent
+                                // collapses to plain arithmetic instead of needing the
+                                // dynamic `o`/`c` sanitization used there. `Dot4U8Packed`
+                                // zero-extends each byte with a shift and mask;
+                                // `Dot4I8Packed` sign-extends it by shifting the byte up
+                                // to the top of a 32-bit `int` and back down with an
+                                // arithmetic right shift.
+                                if conversion == "int" {
+                                    write!(self.out, "((int(")?;
+                                    self.write_expr(arg, ctx)?;
+                                    write!(self.out, ") << {}) >> 24)", 24 - i * 8)?;
+
+                                    write!(self.out, " * ((int(")?;
+                                    self.write_expr(arg1, ctx)?;
+                                    write!(self.out, ") << {}) >> 24)", 24 - i * 8)?;
+                                } else {
+                                    write!(self.out, "((")?;
+                                    self.write_expr(arg, ctx)?;
+                                    write!(self.out, " >> {}u) & 0xFFu)", i * 8)?;
+
+                                    write!(self.out, " * ((")?;
+                                    self.write_expr(arg1, ctx)?;
+                                    write!(self.out, " >> {}u) & 0xFFu)", i * 8)?;
+                                }
+                            }
 
                             if i != 3 {
                                 write!(self.out, " + ")?;
@@ -3800,32 +5035,82 @@ impl<'a, W: Write> Writer<'a, W> {
                     },
                     // bits
                     Mf::CountTrailingZeros => {
-                        match *ctx.resolve_type(arg, &self.module.types) {
-                            TypeInner::Vector { size, scalar, .. } => {
-                                let s = common::vector_size_str(size);
-                                if let crate::ScalarKind::Uint = scalar.kind {
-                                    write!(self.out, "min(uvec{s}(findLSB(")?;
-                                    self.write_expr(arg, ctx)?;
-                                    write!(self.out, ")), uvec{s}(32u))")?;
-                                } else {
-                                    write!(self.out, "ivec{s}(min(uvec{s}(findLSB(")?;
-                                    self.write_expr(arg, ctx)?;
-                                    write!(self.out, ")), uvec{s}(32u)))")?;
+                        if self.options.version.supports_integer_functions() {
+                            match *ctx.resolve_type(arg, &self.module.types) {
+                                TypeInner::Vector { size, scalar, .. } => {
+                                    let s = common::vector_size_str(size);
+                                    if let crate::ScalarKind::Uint = scalar.kind {
+                                        write!(self.out, "min(uvec{s}(findLSB(")?;
+                                        self.write_expr(arg, ctx)?;
+                                        write!(self.out, ")), uvec{s}(32u))")?;
+                                    } else {
+                                        write!(self.out, "ivec{s}(min(uvec{s}(findLSB(")?;
+                                        self.write_expr(arg, ctx)?;
+                                        write!(self.out, ")), uvec{s}(32u)))")?;
+                                    }
                                 }
-                            }
-                            TypeInner::Scalar(scalar) => {
-                                if let crate::ScalarKind::Uint = scalar.kind {
-                                    write!(self.out, "min(uint(findLSB(")?;
-                                    self.write_expr(arg, ctx)?;
-                                    write!(self.out, ")), 32u)")?;
-                                } else {
-                                    write!(self.out, "int(min(uint(findLSB(")?;
-                                    self.write_expr(arg, ctx)?;
-                                    write!(self.out, ")), 32u))")?;
+                                TypeInner::Scalar(scalar) => {
+                                    if let crate::ScalarKind::Uint = scalar.kind {
+                                        write!(self.out, "min(uint(findLSB(")?;
+                                        self.write_expr(arg, ctx)?;
+                                        write!(self.out, ")), 32u)")?;
+                                    } else {
+                                        write!(self.out, "int(min(uint(findLSB(")?;
+                                        self.write_expr(arg, ctx)?;
+                                        write!(self.out, ")), 32u))")?;
+                                    }
                                 }
-                            }
-                            _ => unreachable!(),
-                        };
+                                _ => unreachable!(),
+                            };
+                        } else {
+                            // `findLSB`/`bitCount` both need `GL_ARB_gpu_shader5`
+                            // (or ES 3.10). Without them, isolate the lowest set
+                            // bit via `x & -x` and count the bits below it with
+                            // the same SWAR popcount used for `CountOneBits`; a
+                            // zero input isolates to zero, which underflows to
+                            // all-ones, whose popcount is 32 — matching
+                            // `findLSB`-plus-clamp's "32 when no bit is set"
+                            // convention for free, with no separate `min` needed.
+                            match *ctx.resolve_type(arg, &self.module.types) {
+                                TypeInner::Vector { size, scalar } => {
+                                    let s = common::vector_size_str(size);
+                                    let utype = format!("uvec{s}");
+                                    let result_type = if let crate::ScalarKind::Uint = scalar.kind
+                                    {
+                                        utype.clone()
+                                    } else {
+                                        format!("ivec{s}")
+                                    };
+                                    let write_mask = |this: &mut Self| -> BackendResult {
+                                        write!(this.out, "({utype}(")?;
+                                        this.write_expr(arg, ctx)?;
+                                        write!(this.out, ") & -{utype}(")?;
+                                        this.write_expr(arg, ctx)?;
+                                        write!(this.out, ")) - 1u")?;
+                                        Ok(())
+                                    };
+                                    self.write_popcount_polyfill(&result_type, &utype, 32, write_mask)?;
+                                }
+                                TypeInner::Scalar(scalar) => {
+                                    let result_type = if let crate::ScalarKind::Uint = scalar.kind
+                                    {
+                                        "uint"
+                                    } else {
+                                        "int"
+                                    };
+                                    let write_mask = |this: &mut Self| -> BackendResult {
+                                        write!(this.out, "(uint(")?;
+                                        this.write_expr(arg, ctx)?;
+                                        write!(this.out, ") & -uint(")?;
+                                        this.write_expr(arg, ctx)?;
+                                        write!(this.out, ")) - 1u")?;
+                                        Ok(())
+                                    };
+                                    self.write_popcount_polyfill(result_type, "uint", 32, write_mask)?;
+                                }
+                                _ => unreachable!(),
+                            };
+                        }
                         return Ok(());
                     }
                     Mf::CountLeadingZeros => {
@@ -3900,8 +5185,65 @@ impl<'a, W: Write> Writer<'a, W> {
 
                         return Ok(());
                     }
-                    Mf::CountOneBits => "bitCount",
-                    Mf::ReverseBits => "bitfieldReverse",
+                    Mf::CountOneBits => {
+                        let (bits, itype, utype) = self.int_polyfill_types(arg, ctx);
+                        // `bitCount` has no 64-bit overload in any
+                        // profile/extension, so 64-bit operands always take
+                        // the polyfill below, regardless of GLSL version.
+                        if bits == 32 && self.options.version.supports_integer_functions() {
+                            "bitCount"
+                        } else {
+                            let is_uint = matches!(
+                                ctx.resolve_type(arg, &self.module.types).scalar_kind(),
+                                Some(crate::ScalarKind::Uint)
+                            );
+                            let result_type = if is_uint { utype.clone() } else { itype };
+                            self.write_popcount_polyfill(&result_type, &utype, bits, |this| {
+                                write!(this.out, "{utype}(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, ")")?;
+                                Ok(())
+                            })?;
+                            return Ok(());
+                        }
+                    }
+                    Mf::ReverseBits => {
+                        if self.options.version.supports_integer_functions() {
+                            "bitfieldReverse"
+                        } else {
+                            let (result_type, utype) =
+                                match *ctx.resolve_type(arg, &self.module.types) {
+                                    TypeInner::Vector { size, scalar } => {
+                                        let s = common::vector_size_str(size);
+                                        let utype = format!("uvec{s}");
+                                        let result_type =
+                                            if let crate::ScalarKind::Uint = scalar.kind {
+                                                utype.clone()
+                                            } else {
+                                                format!("ivec{s}")
+                                            };
+                                        (result_type, utype)
+                                    }
+                                    TypeInner::Scalar(scalar) => {
+                                        let result_type =
+                                            if let crate::ScalarKind::Uint = scalar.kind {
+                                                "uint".to_string()
+                                            } else {
+                                                "int".to_string()
+                                            };
+                                        (result_type, "uint".to_string())
+                                    }
+                                    _ => unreachable!(),
+                                };
+                            self.write_bitreverse_polyfill(&result_type, &utype, |this| {
+                                write!(this.out, "{utype}(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, ")")?;
+                                Ok(())
+                            })?;
+                            return Ok(());
+                        }
+                    }
                     Mf::ExtractBits => {
                         // The behavior of ExtractBits is undefined when offset + count > bit_width. We need
                         // to first sanitize the offset and count first. If we don't do this, AMD and Intel chips
@@ -3917,83 +5259,426 @@ impl<'a, W: Write> Writer<'a, W> {
                         // bitfieldExtract(x, o, c)
                         //
                         // extract_bits(e, min(offset, w), min(count, w - min(offset, w))))
-                        let scalar_bits = ctx
-                            .resolve_type(arg, &self.module.types)
-                            .scalar_width()
-                            .unwrap()
-                            * 8;
+                        let (bits, itype, utype) = self.int_polyfill_types(arg, ctx);
+                        let scalar_bits = bits;
 
-                        write!(self.out, "bitfieldExtract(")?;
-                        self.write_expr(arg, ctx)?;
-                        write!(self.out, ", int(min(")?;
-                        self.write_expr(arg1.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u)), int(min(",)?;
-                        self.write_expr(arg2.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u - min(")?;
-                        self.write_expr(arg1.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u))))")?;
+                        // `bitfieldExtract` has no 64-bit overload in any
+                        // profile/extension, so 64-bit operands always take
+                        // the shift-and-mask fallback below.
+                        if bits == 32 && self.options.version.supports_integer_functions() {
+                            write!(self.out, "bitfieldExtract(")?;
+                            self.write_expr(arg, ctx)?;
+                            write!(self.out, ", int(min(")?;
+                            self.write_expr(arg1.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u)), int(min(",)?;
+                            self.write_expr(arg2.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u - min(")?;
+                            self.write_expr(arg1.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u))))")?;
+                        } else {
+                            // Shift-and-mask equivalent of the above, needed
+                            // both for profiles lacking `bitfieldExtract`
+                            // (`GL_ARB_gpu_shader5`/ES 3.10) and for 64-bit
+                            // operands, using the same `o`/`c` sanitization. A
+                            // zero count is special-cased to zero, matching
+                            // what `bitfieldExtract` itself guarantees for
+                            // `count == 0` — shifting by the full bit width,
+                            // which `w - c` would otherwise do, is undefined.
+                            let write_o = |this: &mut Self| -> BackendResult {
+                                write!(this.out, "min(")?;
+                                this.write_expr(arg1.unwrap(), ctx)?;
+                                write!(this.out, ", {scalar_bits}u)")?;
+                                Ok(())
+                            };
+                            let write_c = |this: &mut Self| -> BackendResult {
+                                write!(this.out, "min(")?;
+                                this.write_expr(arg2.unwrap(), ctx)?;
+                                write!(this.out, ", {scalar_bits}u - ")?;
+                                write_o(this)?;
+                                write!(this.out, ")")?;
+                                Ok(())
+                            };
+                            let is_uint = matches!(
+                                ctx.resolve_type(arg, &self.module.types).scalar_kind(),
+                                Some(crate::ScalarKind::Uint)
+                            );
+                            let all_ones = if bits == 64 {
+                                "0xFFFFFFFFFFFFFFFFUL"
+                            } else {
+                                "0xFFFFFFFFu"
+                            };
+
+                            write!(self.out, "(")?;
+                            write_c(self)?;
+                            if is_uint {
+                                write!(self.out, " == 0u ? {utype}(0u) : ((")?;
+                                write!(self.out, "{utype}(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ") >> ")?;
+                                write_o(self)?;
+                                write!(self.out, ") & ({all_ones} >> ({scalar_bits}u - ")?;
+                                write_c(self)?;
+                                write!(self.out, "))))")?;
+                            } else {
+                                write!(self.out, " == 0u ? {itype}(0) : ")?;
+                                write!(self.out, "{itype}(({itype}(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ") << ({scalar_bits}u - ")?;
+                                write_c(self)?;
+                                write!(self.out, " - ")?;
+                                write_o(self)?;
+                                write!(self.out, ")) >> ({scalar_bits}u - ")?;
+                                write_c(self)?;
+                                write!(self.out, ")))")?;
+                            }
+                        }
 
                         return Ok(());
                     }
                     Mf::InsertBits => {
                         // InsertBits has the same considerations as ExtractBits above
-                        let scalar_bits = ctx
-                            .resolve_type(arg, &self.module.types)
-                            .scalar_width()
-                            .unwrap()
-                            * 8;
+                        let (bits, itype, utype) = self.int_polyfill_types(arg, ctx);
+                        let scalar_bits = bits;
+
+                        // `bitfieldInsert` has no 64-bit overload in any
+                        // profile/extension either, so 64-bit operands always
+                        // take the shift-and-mask fallback below.
+                        if bits == 32 && self.options.version.supports_integer_functions() {
+                            write!(self.out, "bitfieldInsert(")?;
+                            self.write_expr(arg, ctx)?;
+                            write!(self.out, ", ")?;
+                            self.write_expr(arg1.unwrap(), ctx)?;
+                            write!(self.out, ", int(min(")?;
+                            self.write_expr(arg2.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u)), int(min(",)?;
+                            self.write_expr(arg3.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u - min(")?;
+                            self.write_expr(arg2.unwrap(), ctx)?;
+                            write!(self.out, ", {scalar_bits}u))))")?;
+                        } else {
+                            // Shift-and-mask equivalent of the above for
+                            // profiles lacking `bitfieldInsert`
+                            // (`GL_ARB_gpu_shader5`/ES 3.10), using the same
+                            // `o`/`c` sanitization: build a `mask` with `c`
+                            // one-bits starting at position `o`, then splice
+                            // `insert` into `base` through it. A zero count
+                            // degenerates to an all-zero mask, leaving `base`
+                            // untouched, matching `bitfieldInsert`'s own
+                            // guarantee for `count == 0`.
+                            let write_o = |this: &mut Self| -> BackendResult {
+                                write!(this.out, "min(")?;
+                                this.write_expr(arg2.unwrap(), ctx)?;
+                                write!(this.out, ", {scalar_bits}u)")?;
+                                Ok(())
+                            };
+                            let write_c = |this: &mut Self| -> BackendResult {
+                                write!(this.out, "min(")?;
+                                this.write_expr(arg3.unwrap(), ctx)?;
+                                write!(this.out, ", {scalar_bits}u - ")?;
+                                write_o(this)?;
+                                write!(this.out, ")")?;
+                                Ok(())
+                            };
+                            let zero = if bits == 64 { "0UL" } else { "0u" };
+                            let all_ones = if bits == 64 {
+                                "0xFFFFFFFFFFFFFFFFUL"
+                            } else {
+                                "0xFFFFFFFFu"
+                            };
+                            let write_mask = |this: &mut Self| -> BackendResult {
+                                write!(this.out, "(")?;
+                                write_c(this)?;
+                                write!(
+                                    this.out,
+                                    " == 0u ? {utype}({zero}) : ({all_ones} >> ({scalar_bits}u - "
+                                )?;
+                                write_c(this)?;
+                                write!(this.out, ")) << ")?;
+                                write_o(this)?;
+                                write!(this.out, ")")?;
+                                Ok(())
+                            };
+                            let is_uint = matches!(
+                                ctx.resolve_type(arg, &self.module.types).scalar_kind(),
+                                Some(crate::ScalarKind::Uint)
+                            );
 
-                        write!(self.out, "bitfieldInsert(")?;
-                        self.write_expr(arg, ctx)?;
-                        write!(self.out, ", ")?;
-                        self.write_expr(arg1.unwrap(), ctx)?;
-                        write!(self.out, ", int(min(")?;
-                        self.write_expr(arg2.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u)), int(min(",)?;
-                        self.write_expr(arg3.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u - min(")?;
-                        self.write_expr(arg2.unwrap(), ctx)?;
-                        write!(self.out, ", {scalar_bits}u))))")?;
+                            if !is_uint {
+                                write!(self.out, "{itype}(")?;
+                            }
+                            write!(self.out, "(({utype}(")?;
+                            self.write_expr(arg, ctx)?;
+                            write!(self.out, ") & ~")?;
+                            write_mask(self)?;
+                            write!(self.out, ") | (({utype}(")?;
+                            self.write_expr(arg1.unwrap(), ctx)?;
+                            write!(self.out, ") << ")?;
+                            write_o(self)?;
+                            write!(self.out, ") & ")?;
+                            write_mask(self)?;
+                            write!(self.out, "))")?;
+                            if !is_uint {
+                                write!(self.out, ")")?;
+                            }
+                        }
 
                         return Ok(());
                     }
-                    Mf::FirstTrailingBit => "findLSB",
-                    Mf::FirstLeadingBit => "findMSB",
-                    // data packing
-                    Mf::Pack4x8snorm => {
-                        if self.options.version.supports_pack_unpack_4x8() {
-                            "packSnorm4x8"
+                    Mf::FirstTrailingBit => {
+                        let (bits, itype, utype) = self.int_polyfill_types(arg, ctx);
+                        // `findLSB` has no 64-bit overload in any
+                        // profile/extension, so 64-bit operands always take
+                        // the polyfill below.
+                        if bits == 32 && self.options.version.supports_integer_functions() {
+                            "findLSB"
                         } else {
-                            // polyfill should go here. Needs a corresponding entry in `need_bake_expression`
-                            return Err(Error::UnsupportedExternal("packSnorm4x8".into()));
+                            match *ctx.resolve_type(arg, &self.module.types) {
+                                TypeInner::Vector { size, .. } => {
+                                    self.write_value_type(
+                                        ctx.resolve_type(arg, &self.module.types),
+                                    )?;
+                                    write!(self.out, "(")?;
+                                    for i in 0..size as usize {
+                                        if i != 0 {
+                                            write!(self.out, ", ")?;
+                                        }
+                                        self.write_first_trailing_bit_polyfill(
+                                            arg,
+                                            ctx,
+                                            bits,
+                                            &itype,
+                                            &utype,
+                                            Some(i),
+                                        )?;
+                                    }
+                                    write!(self.out, ")")?;
+                                }
+                                _ => {
+                                    self.write_first_trailing_bit_polyfill(
+                                        arg, ctx, bits, &itype, &utype, None,
+                                    )?;
+                                }
+                            }
+                            return Ok(());
                         }
                     }
-                    Mf::Pack4x8unorm => {
-                        if self.options.version.supports_pack_unpack_4x8() {
-                            "packUnorm4x8"
+                    Mf::FirstLeadingBit => {
+                        let (bits, itype, utype) = self.int_polyfill_types(arg, ctx);
+                        // `findMSB` has no 64-bit overload either, so 64-bit
+                        // operands always take the polyfill below.
+                        if bits == 32 && self.options.version.supports_integer_functions() {
+                            "findMSB"
                         } else {
-                            return Err(Error::UnsupportedExternal("packUnorm4x8".to_owned()));
+                            let is_sint = matches!(
+                                ctx.resolve_type(arg, &self.module.types).scalar_kind(),
+                                Some(crate::ScalarKind::Sint)
+                            );
+                            match *ctx.resolve_type(arg, &self.module.types) {
+                                TypeInner::Vector { size, .. } => {
+                                    self.write_value_type(
+                                        ctx.resolve_type(arg, &self.module.types),
+                                    )?;
+                                    write!(self.out, "(")?;
+                                    for i in 0..size as usize {
+                                        if i != 0 {
+                                            write!(self.out, ", ")?;
+                                        }
+                                        self.write_first_leading_bit_polyfill(
+                                            arg,
+                                            ctx,
+                                            bits,
+                                            &itype,
+                                            &utype,
+                                            is_sint,
+                                            Some(i),
+                                        )?;
+                                    }
+                                    write!(self.out, ")")?;
+                                }
+                                _ => {
+                                    self.write_first_leading_bit_polyfill(
+                                        arg, ctx, bits, &itype, &utype, is_sint, None,
+                                    )?;
+                                }
+                            }
+                            return Ok(());
                         }
                     }
-                    Mf::Pack2x16snorm => {
-                        if self.options.version.supports_pack_unpack_snorm_2x16() {
-                            "packSnorm2x16"
-                        } else {
-                            return Err(Error::UnsupportedExternal("packSnorm2x16".to_owned()));
-                        }
+                    // data packing
+                    //
+                    // `Pack2x16snorm`/`Pack2x16unorm`/`Pack4x8snorm`/`Pack4x8unorm` below
+                    // already fall back to hand-written clamp/round/shift arithmetic when
+                    // `supports_pack_unpack_*` is false, mirroring the `Unpack*` arms further
+                    // down; no native `pack*` call is emitted on profiles lacking it.
+                    Mf::Pack4x8snorm => {
+                        if self.options.version.supports_pack_unpack_4x8() {
+                            "packSnorm4x8"
+                        } else {
+                            let scale = 127;
+
+                            let write_lane = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(uint(int(round(clamp(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, "[{index}], -1.0, 1.0) * {scale}.0))) & 0xFFu)")?;
+                                Ok(())
+                            };
+                            write!(self.out, "(")?;
+                            write_lane(self, 0)?;
+                            write!(self.out, " | (")?;
+                            write_lane(self, 1)?;
+                            write!(self.out, " << 8) | (")?;
+                            write_lane(self, 2)?;
+                            write!(self.out, " << 16) | (")?;
+                            write_lane(self, 3)?;
+                            write!(self.out, " << 24))")?;
+                            return Ok(());
+                        }
+                    }
+                    Mf::Pack4x8unorm => {
+                        if self.options.version.supports_pack_unpack_4x8() {
+                            "packUnorm4x8"
+                        } else {
+                            let scale = 255;
+
+                            let write_lane = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(uint(round(clamp(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, "[{index}], 0.0, 1.0) * {scale}.0)) & 0xFFu)")?;
+                                Ok(())
+                            };
+                            write!(self.out, "(")?;
+                            write_lane(self, 0)?;
+                            write!(self.out, " | (")?;
+                            write_lane(self, 1)?;
+                            write!(self.out, " << 8) | (")?;
+                            write_lane(self, 2)?;
+                            write!(self.out, " << 16) | (")?;
+                            write_lane(self, 3)?;
+                            write!(self.out, " << 24))")?;
+                            return Ok(());
+                        }
+                    }
+                    Mf::Pack2x16snorm => {
+                        if self.options.version.supports_pack_unpack_snorm_2x16() {
+                            "packSnorm2x16"
+                        } else {
+                            let scale = 32767;
+
+                            let write_lane = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(uint(int(round(clamp(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(
+                                    this.out,
+                                    "[{index}], -1.0, 1.0) * {scale}.0))) & 0xFFFFu)"
+                                )?;
+                                Ok(())
+                            };
+                            write!(self.out, "(")?;
+                            write_lane(self, 0)?;
+                            write!(self.out, " | (")?;
+                            write_lane(self, 1)?;
+                            write!(self.out, " << 16))")?;
+                            return Ok(());
+                        }
                     }
                     Mf::Pack2x16unorm => {
                         if self.options.version.supports_pack_unpack_unorm_2x16() {
                             "packUnorm2x16"
                         } else {
-                            return Err(Error::UnsupportedExternal("packUnorm2x16".to_owned()));
+                            let scale = 65535;
+
+                            let write_lane = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(uint(round(clamp(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, "[{index}], 0.0, 1.0) * {scale}.0)) & 0xFFFFu)")?;
+                                Ok(())
+                            };
+                            write!(self.out, "(")?;
+                            write_lane(self, 0)?;
+                            write!(self.out, " | (")?;
+                            write_lane(self, 1)?;
+                            write!(self.out, " << 16))")?;
+                            return Ok(());
                         }
                     }
                     Mf::Pack2x16float => {
                         if self.options.version.supports_pack_unpack_half_2x16() {
                             "packHalf2x16"
                         } else {
-                            return Err(Error::UnsupportedExternal("packHalf2x16".to_owned()));
+                            // `packHalf2x16` itself is unavailable, so convert each
+                            // lane's binary32 bits to binary16 by hand: split out the
+                            // sign, the biased exponent `e` and the mantissa `m`, then
+                            // branch on `e` the way the binary16 encoding does. `e` is
+                            // the raw biased IEEE-754 binary32 exponent, so each
+                            // threshold sits 112 (the 127 - 15 binary32/binary16 bias
+                            // difference) above the binary16 exponent it stands for;
+                            // below 103 the
+                            // result underflows to (signed) zero, above 142 it
+                            // overflows to (signed) infinity, 103..=112 is binary16's
+                            // denormal range (reconstruct the implicit leading 1 and
+                            // shift it down by how far `e` sits below a normal
+                            // exponent), and otherwise it's a normal binary16, rounded
+                            // to nearest-even by biasing `m` before truncating its low
+                            // 13 bits.
+                            let write_bits = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "floatBitsToUint(")?;
+                                this.write_expr(arg, ctx)?;
+                                write!(this.out, "[{index}])")?;
+                                Ok(())
+                            };
+                            let write_sign = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "((")?;
+                                write_bits(this, index)?;
+                                write!(this.out, " >> 16u) & 0x8000u)")?;
+                                Ok(())
+                            };
+                            let write_e = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "((")?;
+                                write_bits(this, index)?;
+                                write!(this.out, " >> 23u) & 0xFFu)")?;
+                                Ok(())
+                            };
+                            let write_m = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(")?;
+                                write_bits(this, index)?;
+                                write!(this.out, " & 0x7FFFFFu)")?;
+                                Ok(())
+                            };
+                            let write_lane = |this: &mut Self, index: usize| -> BackendResult {
+                                write!(this.out, "(")?;
+                                write_e(this, index)?;
+                                write!(this.out, " < 103u ? ")?;
+                                write_sign(this, index)?;
+                                write!(this.out, " : (")?;
+                                write_e(this, index)?;
+                                write!(this.out, " > 142u ? (")?;
+                                write_sign(this, index)?;
+                                write!(this.out, " | 0x7C00u) : (")?;
+                                write_e(this, index)?;
+                                write!(this.out, " <= 112u ? (")?;
+                                write_sign(this, index)?;
+                                write!(this.out, " | ((")?;
+                                write_m(this, index)?;
+                                write!(this.out, " | 0x800000u) >> (125u - ")?;
+                                write_e(this, index)?;
+                                write!(this.out, "))) : (")?;
+                                write_sign(this, index)?;
+                                write!(this.out, " | (((")?;
+                                write_e(this, index)?;
+                                write!(this.out, " - 112u) << 10) + ((")?;
+                                write_m(this, index)?;
+                                write!(this.out, " + 0xFFFu + ((")?;
+                                write_m(this, index)?;
+                                write!(this.out, " >> 13u) & 1u)) >> 13u)))")?;
+                                write!(this.out, ")))")?;
+                                Ok(())
+                            };
+                            write!(self.out, "(")?;
+                            write_lane(self, 0)?;
+                            write!(self.out, " | (")?;
+                            write_lane(self, 1)?;
+                            write!(self.out, " << 16))")?;
+                            return Ok(());
                         }
                     }
 
@@ -4038,7 +5723,32 @@ impl<'a, W: Write> Writer<'a, W> {
                         if self.options.version.supports_pack_unpack_half_2x16() {
                             "unpackHalf2x16"
                         } else {
-                            return Err(Error::UnsupportedExternal("unpackHalf2x16".into()));
+                            // Reverses the bit layout `Pack2x16float`'s fallback above
+                            // produces: a zero exponent (zero/denormal) scales the
+                            // mantissa by 2^-24, an all-ones exponent (inf/NaN) maps to
+                            // binary32's all-ones exponent, and otherwise the exponent
+                            // bias difference is re-added and the combined
+                            // exponent/mantissa bits are shifted back up to binary32's
+                            // position, then reinterpreted as float.
+                            let write_lane = |this: &mut Self, shift: &str| -> BackendResult {
+                                for part in [
+                                    "(((", " & 0x7C00u) == 0u) ? ((", " & 0x8000u) != 0u ? -1.0 : 1.0) * float(",
+                                    " & 0x3FFu) * 5.9604645e-8 : uintBitsToFloat(((", " & 0x8000u) << 16) | ((",
+                                    " & 0x7C00u) == 0x7C00u ? 0x7F800000u | ((", " & 0x3FFu) << 13) : ((",
+                                ] {
+                                    write!(this.out, "{part}(")?;
+                                    this.write_expr(arg, ctx)?;
+                                    write!(this.out, "{shift})")?;
+                                }
+                                write!(this.out, " & 0x7FFFu) + 114688u) << 13)))")?;
+                                Ok(())
+                            };
+                            write!(self.out, "vec2(")?;
+                            write_lane(self, "")?;
+                            write!(self.out, ", ")?;
+                            write_lane(self, " >> 16")?;
+                            write!(self.out, ")")?;
+                            return Ok(());
                         }
                     }
                     Mf::Unpack2x16snorm => {
@@ -4282,6 +5992,56 @@ impl<'a, W: Write> Writer<'a, W> {
 
                         let source_kind = inner.scalar_kind().unwrap();
 
+                        // Double-precision bitcasts have no native GLSL
+                        // reinterpret function — even `GL_ARB_gpu_shader_int64`
+                        // doesn't add a `doubleBitsToInt64` — so synthesize
+                        // one from `unpackDouble2x32`/`packDouble2x32`, which
+                        // move a double's bits through the `uvec2` of its
+                        // low/high 32-bit halves. `GL_ARB_gpu_shader_fp64` is
+                        // already required module-wide by
+                        // `collect_required_extensions`'s `uses_fp64` scan
+                        // whenever an `f64` type appears anywhere, so no
+                        // extra gating is needed here; there's no
+                        // `features.rs` capability to route it through in
+                        // this tree (`mod features` is declared but its
+                        // source isn't part of this checkout).
+                        if inner.scalar_width() == Some(8)
+                            && matches!(
+                                (source_kind, target_kind),
+                                (Sk::Float, Sk::Sint | Sk::Uint) | (Sk::Sint | Sk::Uint, Sk::Float)
+                            )
+                        {
+                            match *inner {
+                                TypeInner::Vector { size, .. } => {
+                                    self.write_value_type(target_vector_type.as_ref().unwrap())?;
+                                    write!(self.out, "(")?;
+                                    for i in 0..size as usize {
+                                        if i != 0 {
+                                            write!(self.out, ", ")?;
+                                        }
+                                        self.write_double_bitcast(
+                                            expr,
+                                            ctx,
+                                            source_kind,
+                                            target_kind,
+                                            Some(i),
+                                        )?;
+                                    }
+                                    write!(self.out, ")")?;
+                                }
+                                _ => {
+                                    self.write_double_bitcast(
+                                        expr,
+                                        ctx,
+                                        source_kind,
+                                        target_kind,
+                                        None,
+                                    )?;
+                                }
+                            }
+                            return Ok(());
+                        }
+
                         match (source_kind, target_kind, target_vector_type) {
                             // No conversion needed
                             (Sk::Sint, Sk::Sint, _)
@@ -4334,9 +6094,46 @@ impl<'a, W: Write> Writer<'a, W> {
                 self.write_expr(expr, ctx)?;
                 write!(self.out, ".length())")?
             }
-            // not supported yet
-            Expression::RayQueryGetIntersection { .. }
-            | Expression::RayQueryVertexPositions { .. } => unreachable!(),
+            // Build a `RayIntersection`-shaped struct literal out of the individual
+            // `rayQueryGetIntersection*EXT` built-ins, one call per struct member,
+            // matched by name since GLSL has no single call that returns the
+            // aggregate the way the IR models it.
+            Expression::RayQueryGetIntersection { query, committed } => {
+                let ty_handle = ctx.info[expr].ty.handle().ok_or_else(|| {
+                    Error::Custom("ray query intersection result has no named struct type".to_owned())
+                })?;
+                let TypeInner::Struct { ref members, .. } = self.module.types[ty_handle].inner
+                else {
+                    return Err(Error::Custom(
+                        "ray query intersection result must be a struct".to_owned(),
+                    ));
+                };
+                let struct_name = &self.names[&NameKey::Type(ty_handle)];
+                write!(self.out, "{struct_name}(")?;
+                for index in 0..members.len() {
+                    if index != 0 {
+                        write!(self.out, ", ")?;
+                    }
+                    let field_name = &self.names[&NameKey::StructMember(ty_handle, index as u32)];
+                    let builtin = ray_intersection_builtin(field_name).ok_or_else(|| {
+                        Error::Custom(format!(
+                            "unrecognized ray query intersection field `{field_name}`"
+                        ))
+                    })?;
+                    write!(self.out, "{builtin}(")?;
+                    self.write_expr(query, ctx)?;
+                    write!(self.out, ", {committed})")?;
+                }
+                write!(self.out, ")")?;
+            }
+            // Vertex position fetch needs `GL_EXT_ray_tracing_position_fetch`, a
+            // separate extension this backend doesn't request; fail loudly
+            // instead of emitting code that won't compile.
+            Expression::RayQueryVertexPositions { .. } => {
+                return Err(Error::Custom(
+                    "ray query vertex position fetch is not supported by this backend".to_owned(),
+                ));
+            }
         }
 
         Ok(())
@@ -4372,6 +6169,433 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Bakes `expr` into a fresh named local unless it's already named.
+    ///
+    /// `write_expr` always checks [`Self::named_expressions`] first and
+    /// writes the cached name instead of re-emitting the expression, so
+    /// calling this before a statement that will reference `expr` more than
+    /// once (as `clamp_to_edge` sampling does with `image`) guarantees it's
+    /// evaluated exactly once rather than duplicated at each use site. Must
+    /// be called before that statement is written, the same way
+    /// [`Self::write_clamped_lod`] pre-bakes the clamped LOD for
+    /// `Restrict`-checked image loads.
+    fn cache_expr_for_reuse(
+        &mut self,
+        ctx: &back::FunctionCtx,
+        expr: Handle<crate::Expression>,
+        level: back::Level,
+    ) -> BackendResult {
+        if self.named_expressions.get(&expr).is_some() {
+            return Ok(());
+        }
+
+        let name = Baked(expr).to_string();
+        write!(self.out, "{level}")?;
+        let ty = ctx.info[expr].ty.inner_with(&self.module.types);
+        self.write_value_type(ty)?;
+        write!(self.out, " {name} = ")?;
+        self.write_expr(expr, ctx)?;
+        writeln!(self.out, ";")?;
+        self.named_expressions.insert(expr, name);
+
+        Ok(())
+    }
+
+    /// Returns `(bits, itype, utype)` — the bit width (32 or 64) and the
+    /// signed/unsigned GLSL type names (scalar or vector, as appropriate) to
+    /// use when polyfilling an integer bit-manipulation builtin for `arg`.
+    /// 64-bit operands need `int64_t`/`uint64_t` (or `i64vec`/`u64vec`)
+    /// instead of `int`/`uint`; the type itself is already guarded behind
+    /// `GL_ARB_gpu_shader_int64` by [`Writer::collect_required_extensions`]'s
+    /// module-wide scan, so callers only need the width to pick the right
+    /// literal suffixes and constants.
+    fn int_polyfill_types(
+        &self,
+        arg: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+    ) -> (u32, String, String) {
+        let width = ctx.resolve_type(arg, &self.module.types).scalar_width();
+        match *ctx.resolve_type(arg, &self.module.types) {
+            TypeInner::Vector { size, .. } => {
+                let s = common::vector_size_str(size);
+                if width == Some(8) {
+                    (64, format!("i64vec{s}"), format!("u64vec{s}"))
+                } else {
+                    (32, format!("ivec{s}"), format!("uvec{s}"))
+                }
+            }
+            TypeInner::Scalar(_) => {
+                if width == Some(8) {
+                    (64, "int64_t".to_string(), "uint64_t".to_string())
+                } else {
+                    (32, "int".to_string(), "uint".to_string())
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Writes a population-count (set-bit count) polyfill for GLSL profiles
+    /// lacking `bitCount` (`!Version::supports_integer_functions`), or for
+    /// 64-bit operands (for which `bitCount` has no overload in any
+    /// profile/extension), using the classic SWAR bit-twiddling reduction.
+    /// `write_value` writes the value to count, already cast to `utype` so
+    /// the shifts don't sign-extend; the result is cast to `itype`, the
+    /// result type chosen by the caller.
+    /// `bits` selects the 32-bit or 64-bit mask/multiplier constants.
+    fn write_popcount_polyfill(
+        &mut self,
+        itype: &str,
+        utype: &str,
+        bits: u32,
+        write_value: impl Fn(&mut Self) -> BackendResult,
+    ) -> BackendResult {
+        let (m1, m2, m3, mult, shift) = if bits == 64 {
+            (
+                "0x5555555555555555UL",
+                "0x3333333333333333UL",
+                "0x0F0F0F0F0F0F0F0FUL",
+                "0x0101010101010101UL",
+                56u32,
+            )
+        } else {
+            (
+                "0x55555555u",
+                "0x33333333u",
+                "0x0F0F0F0Fu",
+                "0x01010101u",
+                24u32,
+            )
+        };
+        let write_step1 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_value(this)?;
+            write!(this.out, " - ((")?;
+            write_value(this)?;
+            write!(this.out, " >> 1u) & {utype}({m1})))")?;
+            Ok(())
+        };
+        let write_step2 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "((")?;
+            write_step1(this)?;
+            write!(this.out, " & {utype}({m2})) + ((")?;
+            write_step1(this)?;
+            write!(this.out, " >> 2u) & {utype}({m2})))")?;
+            Ok(())
+        };
+        write!(self.out, "{itype}((((")?;
+        write_step2(self)?;
+        write!(self.out, " + (")?;
+        write_step2(self)?;
+        write!(
+            self.out,
+            " >> 4u)) & {utype}({m3})) * {utype}({mult}) >> {shift}u))"
+        )?;
+        Ok(())
+    }
+
+    /// Writes `findLSB`'s polyfill, needed for 64-bit operands (no
+    /// profile/extension gives `findLSB` a 64-bit overload) and for profiles
+    /// lacking `GL_ARB_gpu_shader5`/ES 3.10: isolates the lowest set bit via
+    /// `x & -x` and counts the bits below it with
+    /// [`Self::write_popcount_polyfill`]. Unlike `CountTrailingZeros`'s own
+    /// `x & -x`-based fallback (whose "32 when no bit is set" convention
+    /// falls out of the same formula for free via unsigned underflow),
+    /// `findLSB`'s spec return of `-1` for a zero input needs an explicit
+    /// guard, since GLSL's ternary condition must be scalar, `component`
+    /// subscripts `arg` for one lane of a vector call.
+    fn write_first_trailing_bit_polyfill(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+        bits: u32,
+        itype: &str,
+        utype: &str,
+        component: Option<usize>,
+    ) -> BackendResult {
+        let write_operand = |this: &mut Self| -> BackendResult {
+            write!(this.out, "{utype}(")?;
+            this.write_expr(arg, ctx)?;
+            if let Some(i) = component {
+                write!(this.out, ".{}", back::COMPONENTS[i])?;
+            }
+            write!(this.out, ")")?;
+            Ok(())
+        };
+        let zero = if bits == 64 { "0UL" } else { "0u" };
+        let one = if bits == 64 { "1UL" } else { "1u" };
+        let write_masked = |this: &mut Self| -> BackendResult {
+            write!(this.out, "((")?;
+            write_operand(this)?;
+            write!(this.out, " & -")?;
+            write_operand(this)?;
+            write!(this.out, ") - {one})")?;
+            Ok(())
+        };
+        write!(self.out, "(")?;
+        write_operand(self)?;
+        write!(self.out, " == {zero} ? {itype}(-1) : ")?;
+        self.write_popcount_polyfill(itype, utype, bits, write_masked)?;
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
+    /// Writes `findMSB`'s polyfill, needed for the same reasons as
+    /// [`Self::write_first_trailing_bit_polyfill`]. Uses a smear-then-count
+    /// technique: OR-shifting every bit below the highest set bit into place
+    /// (doubling the shift each pass) turns the value into a run of
+    /// one-bits, so its popcount minus one is the index of the original
+    /// highest set bit — and conveniently also yields `-1` for a zero input
+    /// with no extra guard (`popcount(0) - 1 == -1`), matching `findMSB`'s
+    /// spec. For signed operands, `is_sint` first XORs the value with its
+    /// own sign-extended arithmetic right shift, which reduces "most
+    /// significant bit differing from the sign bit" to the same unsigned
+    /// smear-and-count case (a no-op when non-negative, and correctly yields
+    /// `-1` for both `0` and `-1`, `findMSB`'s two signed special cases).
+    /// `component` subscripts `arg` for one lane of a vector call.
+    fn write_first_leading_bit_polyfill(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+        bits: u32,
+        itype: &str,
+        utype: &str,
+        is_sint: bool,
+        component: Option<usize>,
+    ) -> BackendResult {
+        let write_signed = |this: &mut Self| -> BackendResult {
+            write!(this.out, "{itype}(")?;
+            this.write_expr(arg, ctx)?;
+            if let Some(i) = component {
+                write!(this.out, ".{}", back::COMPONENTS[i])?;
+            }
+            write!(this.out, ")")?;
+            Ok(())
+        };
+        let write_unsigned = |this: &mut Self| -> BackendResult {
+            write!(this.out, "{utype}(")?;
+            this.write_expr(arg, ctx)?;
+            if let Some(i) = component {
+                write!(this.out, ".{}", back::COMPONENTS[i])?;
+            }
+            write!(this.out, ")")?;
+            Ok(())
+        };
+        let write_folded = |this: &mut Self| -> BackendResult {
+            if is_sint {
+                write!(this.out, "{utype}((")?;
+                write_signed(this)?;
+                write!(this.out, ") ^ ((")?;
+                write_signed(this)?;
+                write!(this.out, ") >> {}))", bits - 1)?;
+            } else {
+                write_unsigned(this)?;
+            }
+            Ok(())
+        };
+        let write_smear1 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_folded(this)?;
+            write!(this.out, " | (")?;
+            write_folded(this)?;
+            write!(this.out, " >> 1u))")?;
+            Ok(())
+        };
+        let write_smear2 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_smear1(this)?;
+            write!(this.out, " | (")?;
+            write_smear1(this)?;
+            write!(this.out, " >> 2u))")?;
+            Ok(())
+        };
+        let write_smear3 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_smear2(this)?;
+            write!(this.out, " | (")?;
+            write_smear2(this)?;
+            write!(this.out, " >> 4u))")?;
+            Ok(())
+        };
+        let write_smear4 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_smear3(this)?;
+            write!(this.out, " | (")?;
+            write_smear3(this)?;
+            write!(this.out, " >> 8u))")?;
+            Ok(())
+        };
+        let write_smear5 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_smear4(this)?;
+            write!(this.out, " | (")?;
+            write_smear4(this)?;
+            write!(this.out, " >> 16u))")?;
+            Ok(())
+        };
+        let write_smear6 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(")?;
+            write_smear5(this)?;
+            write!(this.out, " | (")?;
+            write_smear5(this)?;
+            write!(this.out, " >> 32u))")?;
+            Ok(())
+        };
+        write!(self.out, "(")?;
+        if bits == 64 {
+            self.write_popcount_polyfill(itype, utype, bits, write_smear6)?;
+        } else {
+            self.write_popcount_polyfill(itype, utype, bits, write_smear5)?;
+        }
+        write!(self.out, " - 1)")?;
+        Ok(())
+    }
+
+    /// Writes a bit-reversal polyfill for GLSL profiles lacking
+    /// `bitfieldReverse` (`!Version::supports_integer_functions`), via a
+    /// shift-and-mask swap network that exchanges adjacent groups of bits,
+    /// doubling the group size each pass. `write_value`/`itype`/`utype` are
+    /// as in [`Self::write_popcount_polyfill`].
+    fn write_bitreverse_polyfill(
+        &mut self,
+        itype: &str,
+        utype: &str,
+        write_value: impl Fn(&mut Self) -> BackendResult,
+    ) -> BackendResult {
+        let write_step1 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(((")?;
+            write_value(this)?;
+            write!(this.out, " >> 1u) & {utype}(0x55555555u)) | ((")?;
+            write_value(this)?;
+            write!(this.out, " & {utype}(0x55555555u)) << 1u))")?;
+            Ok(())
+        };
+        let write_step2 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(((")?;
+            write_step1(this)?;
+            write!(this.out, " >> 2u) & {utype}(0x33333333u)) | ((")?;
+            write_step1(this)?;
+            write!(this.out, " & {utype}(0x33333333u)) << 2u))")?;
+            Ok(())
+        };
+        let write_step3 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(((")?;
+            write_step2(this)?;
+            write!(this.out, " >> 4u) & {utype}(0x0F0F0F0Fu)) | ((")?;
+            write_step2(this)?;
+            write!(this.out, " & {utype}(0x0F0F0F0Fu)) << 4u))")?;
+            Ok(())
+        };
+        let write_step4 = |this: &mut Self| -> BackendResult {
+            write!(this.out, "(((")?;
+            write_step3(this)?;
+            write!(this.out, " >> 8u) & {utype}(0x00FF00FFu)) | ((")?;
+            write_step3(this)?;
+            write!(this.out, " & {utype}(0x00FF00FFu)) << 8u))")?;
+            Ok(())
+        };
+        write!(self.out, "{itype}((")?;
+        write_step4(self)?;
+        write!(self.out, " >> 16u) | (")?;
+        write_step4(self)?;
+        write!(self.out, " << 16u))")?;
+        Ok(())
+    }
+
+    /// Writes a single signed/unsigned-integer `%`, guarded against two
+    /// cases where GLSL's own `%` is undefined: `right == 0` always, and
+    /// (for signed operands) `left == <type>_MIN && right == -1`, which
+    /// overflows. WGSL defines both as yielding `0`, so they're special-cased
+    /// to it rather than left to trap. All other operands, negative ones
+    /// included, go to GLSL's `%` unchanged. `component`, when `Some`, subscripts
+    /// `left`/`right` for one lane of a vector modulo; `overflow_guard`,
+    /// when `Some(min, neg_one)`, adds the signed overflow check (omit it
+    /// for unsigned operands, which have no such case).
+    fn write_int_modulo(
+        &mut self,
+        left: Handle<crate::Expression>,
+        right: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+        component: Option<usize>,
+        zero: &str,
+        overflow_guard: Option<(&str, &str)>,
+    ) -> BackendResult {
+        let mut write_operand = |this: &mut Self,
+                                  expr: Handle<crate::Expression>|
+         -> BackendResult {
+            this.write_expr(expr, ctx)?;
+            if let Some(i) = component {
+                write!(this.out, ".{}", back::COMPONENTS[i])?;
+            }
+            Ok(())
+        };
+
+        write!(self.out, "(")?;
+        write_operand(self, right)?;
+        write!(self.out, " == {zero}")?;
+        if let Some((min, neg_one)) = overflow_guard {
+            write!(self.out, " || (")?;
+            write_operand(self, left)?;
+            write!(self.out, " == {min} && ")?;
+            write_operand(self, right)?;
+            write!(self.out, " == {neg_one})")?;
+        }
+        write!(self.out, " ? {zero} : ")?;
+        write_operand(self, left)?;
+        write!(self.out, " % ")?;
+        write_operand(self, right)?;
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
+    /// Writes a double-precision bitcast for one scalar or vector lane:
+    /// `f64 -> i64`/`u64` via `unpackDouble2x32` (which returns a double's
+    /// low/high 32-bit halves as a `uvec2`, recombined here into a single
+    /// 64-bit value), or the reverse via `packDouble2x32`. Neither direction
+    /// has a direct native function, since `unpackDouble2x32`/
+    /// `packDouble2x32` only convert between `double` and `uvec2`, not
+    /// `double` and `int64_t`/`uint64_t`. `component`, when `Some`,
+    /// subscripts the source expression for one lane of a vector bitcast.
+    fn write_double_bitcast(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+        source_kind: crate::ScalarKind,
+        target_kind: crate::ScalarKind,
+        component: Option<usize>,
+    ) -> BackendResult {
+        let write_operand = |this: &mut Self| -> BackendResult {
+            this.write_expr(expr, ctx)?;
+            if let Some(i) = component {
+                write!(this.out, ".{}", back::COMPONENTS[i])?;
+            }
+            Ok(())
+        };
+
+        if source_kind == crate::ScalarKind::Float {
+            if target_kind == crate::ScalarKind::Uint {
+                write!(self.out, "(uint64_t(unpackDouble2x32(")?;
+                write_operand(self)?;
+                write!(self.out, ").x) | (uint64_t(unpackDouble2x32(")?;
+                write_operand(self)?;
+                write!(self.out, ").y) << 32))")?;
+            } else {
+                write!(self.out, "int64_t(uint64_t(unpackDouble2x32(")?;
+                write_operand(self)?;
+                write!(self.out, ").x) | (uint64_t(unpackDouble2x32(")?;
+                write_operand(self)?;
+                write!(self.out, ").y) << 32))")?;
+            }
+        } else {
+            write!(self.out, "packDouble2x32(uvec2(uint(uint64_t(")?;
+            write_operand(self)?;
+            write!(self.out, ") & 0xFFFFFFFFUL), uint(uint64_t(")?;
+            write_operand(self)?;
+            write!(self.out, ") >> 32)))")?;
+        }
+        Ok(())
+    }
+
     // Helper method used to retrieve how many elements a coordinate vector
     // for the images operations need.
     fn get_coordinate_vector_size(&self, dim: crate::ImageDimension, arrayed: bool) -> u8 {
@@ -4454,6 +6678,49 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Writes a `lessThan`/`greaterThanEqual` guard testing whether `coordinate` (and
+    /// `array_index`, if present) falls inside `image`'s `imageSize`, for use as the
+    /// condition of an `if` guarding an `imageStore`/`imageAtomic*` call under
+    /// [`BoundsCheckPolicy::ReadZeroSkipWrite`](proc::BoundsCheckPolicy::ReadZeroSkipWrite).
+    ///
+    /// Unlike the `ReadZeroSkipWrite` check in [`Self::write_image_load`], this also
+    /// checks that the coordinate isn't negative, since there's no "valid texel" to
+    /// substitute for an out-of-bounds write the way there is for a load.
+    fn write_image_store_bounds_check(
+        &mut self,
+        ctx: &back::FunctionCtx,
+        dim: crate::ImageDimension,
+        image: Handle<crate::Expression>,
+        coordinate: Handle<crate::Expression>,
+        array_index: Option<Handle<crate::Expression>>,
+    ) -> BackendResult {
+        use crate::ImageDimension as IDim;
+
+        // openGL es doesn't have 1D images so we need workaround it
+        let tex_1d_hack = dim == IDim::D1 && self.options.version.is_es();
+        let vector_size = self.get_coordinate_vector_size(dim, array_index.is_some());
+
+        // Expressions cannot have side effects, so writing `coordinate` twice is fine.
+        if vector_size == 1 {
+            self.write_texture_coord(ctx, vector_size, coordinate, array_index, tex_1d_hack)?;
+            write!(self.out, " < imageSize(")?;
+            self.write_expr(image, ctx)?;
+            write!(self.out, ") && ")?;
+            self.write_texture_coord(ctx, vector_size, coordinate, array_index, tex_1d_hack)?;
+            write!(self.out, " >= 0")?;
+        } else {
+            write!(self.out, "all(lessThan(")?;
+            self.write_texture_coord(ctx, vector_size, coordinate, array_index, tex_1d_hack)?;
+            write!(self.out, ", imageSize(")?;
+            self.write_expr(image, ctx)?;
+            write!(self.out, "))) && all(greaterThanEqual(")?;
+            self.write_texture_coord(ctx, vector_size, coordinate, array_index, tex_1d_hack)?;
+            write!(self.out, ", ivec{vector_size}(0)))")?;
+        }
+
+        Ok(())
+    }
+
     /// Helper method to write the `ImageStore` statement
     fn write_image_store(
         &mut self,
@@ -4462,20 +6729,36 @@ impl<'a, W: Write> Writer<'a, W> {
         coordinate: Handle<crate::Expression>,
         array_index: Option<Handle<crate::Expression>>,
         value: Handle<crate::Expression>,
+        level: back::Level,
     ) -> Result<(), Error> {
         use crate::ImageDimension as IDim;
 
-        // NOTE: openGL requires that `imageStore`s have no effects when the texel is invalid
-        // so we don't need to generate bounds checks (OpenGL 4.2 Core §3.9.20)
-
         // This will only panic if the module is invalid
         let dim = match *ctx.resolve_type(image, &self.module.types) {
             TypeInner::Image { dim, .. } => dim,
             _ => unreachable!(),
         };
 
+        // openGL requires that `imageStore`s have no effects when the texel is invalid
+        // (OpenGL 4.2 Core §3.9.20), so by default we don't need to generate bounds
+        // checks. In practice several GL ES 3.1 drivers don't honor this reliably, so
+        // under `ReadZeroSkipWrite` we guard the store with an explicit `if` instead of
+        // relying on it.
+        let guarded = matches!(
+            self.policies.image_store,
+            proc::BoundsCheckPolicy::ReadZeroSkipWrite
+        );
+        let inner_level = if guarded {
+            write!(self.out, "{level}if (")?;
+            self.write_image_store_bounds_check(ctx, dim, image, coordinate, array_index)?;
+            writeln!(self.out, ") {{")?;
+            level.next()
+        } else {
+            level
+        };
+
         // Begin our call to `imageStore`
-        write!(self.out, "imageStore(")?;
+        write!(self.out, "{inner_level}imageStore(")?;
         self.write_expr(image, ctx)?;
         // Separate the image argument from the coordinates
         write!(self.out, ", ")?;
@@ -4499,10 +6782,15 @@ impl<'a, W: Write> Writer<'a, W> {
         // End the call to `imageStore` and the statement.
         writeln!(self.out, ");")?;
 
+        if guarded {
+            writeln!(self.out, "{level}}}")?;
+        }
+
         Ok(())
     }
 
     /// Helper method to write the `ImageAtomic` statement
+    #[allow(clippy::too_many_arguments)]
     fn write_image_atomic(
         &mut self,
         ctx: &back::FunctionCtx,
@@ -4511,21 +6799,56 @@ impl<'a, W: Write> Writer<'a, W> {
         array_index: Option<Handle<crate::Expression>>,
         fun: crate::AtomicFunction,
         value: Handle<crate::Expression>,
+        level: back::Level,
     ) -> Result<(), Error> {
         use crate::ImageDimension as IDim;
 
-        // NOTE: openGL requires that `imageAtomic`s have no effects when the texel is invalid
-        // so we don't need to generate bounds checks (OpenGL 4.2 Core §3.9.20)
-
         // This will only panic if the module is invalid
-        let dim = match *ctx.resolve_type(image, &self.module.types) {
-            TypeInner::Image { dim, .. } => dim,
+        let (dim, format) = match *ctx.resolve_type(image, &self.module.types) {
+            TypeInner::Image {
+                dim,
+                class: crate::ImageClass::Storage { format, .. },
+                ..
+            } => (dim, format),
             _ => unreachable!(),
         };
 
+        // openGL requires that `imageAtomic`s have no effects when the texel is invalid
+        // (OpenGL 4.2 Core §3.9.20), so by default we don't need to generate bounds
+        // checks. In practice several GL ES 3.1 drivers don't honor this reliably, so
+        // under `ReadZeroSkipWrite` we guard the call with an explicit `if` instead of
+        // relying on it, the same way `write_image_store` does.
+        let guarded = matches!(
+            self.policies.image_store,
+            proc::BoundsCheckPolicy::ReadZeroSkipWrite
+        );
+        let inner_level = if guarded {
+            write!(self.out, "{level}if (")?;
+            self.write_image_store_bounds_check(ctx, dim, image, coordinate, array_index)?;
+            writeln!(self.out, ") {{")?;
+            level.next()
+        } else {
+            level
+        };
+
+        // `imageAtomicCompSwap` has its own argument order (image, coord, compare, value),
+        // so it's written out directly here rather than through the generic
+        // `imageAtomic{fun.to_glsl()}(image, coord, value)` shape below; this mirrors how
+        // `Statement::Atomic` above writes `atomicCompSwap` as a special case rather than
+        // folding it into `AtomicFunction::to_glsl`.
+        let compare = match fun {
+            crate::AtomicFunction::Exchange { compare } => compare,
+            _ => None,
+        };
+
         // Begin our call to `imageAtomic`
-        let fun_str = fun.to_glsl();
-        write!(self.out, "imageAtomic{fun_str}(")?;
+        write!(self.out, "{inner_level}imageAtomic")?;
+        if compare.is_some() {
+            write!(self.out, "CompSwap(")?;
+        } else {
+            let fun_str = fun.to_glsl();
+            write!(self.out, "{fun_str}(")?;
+        }
         self.write_expr(image, ctx)?;
         // Separate the image argument from the coordinates
         write!(self.out, ", ")?;
@@ -4542,17 +6865,54 @@ impl<'a, W: Write> Writer<'a, W> {
             tex_1d_hack,
         )?;
 
-        // Separate the coordinate from the value to write and write the expression
-        // of the value to write.
+        // For compare-exchange, the comparison value comes before the new value. It's
+        // subject to the same `R64Uint` overload resolution as `value` below, so it needs
+        // the same `uint64_t(...)` cast.
+        if let Some(compare_expr) = compare {
+            write!(self.out, ", ")?;
+            if matches!(format, crate::StorageFormat::R64Uint) {
+                write!(self.out, "uint64_t(")?;
+                self.write_expr(compare_expr, ctx)?;
+                write!(self.out, ")")?;
+            } else {
+                self.write_expr(compare_expr, ctx)?;
+            }
+        }
+
+        // Separate the coordinate (and, for compare-exchange, the comparison value) from
+        // the value to write and write the expression of the value to write. On an
+        // `R64Uint` image the `imageAtomic*` overload is resolved by argument type, so
+        // `value` needs an explicit `uint64_t(...)` cast to match the `u64image2D`'s texel
+        // type (a plain `uint` literal/expression would otherwise resolve to the 32-bit
+        // overload and fail to compile).
         write!(self.out, ", ")?;
-        self.write_expr(value, ctx)?;
+        if matches!(format, crate::StorageFormat::R64Uint) {
+            write!(self.out, "uint64_t(")?;
+            self.write_expr(value, ctx)?;
+            write!(self.out, ")")?;
+        } else {
+            self.write_expr(value, ctx)?;
+        }
         // End the call to `imageAtomic` and the statement.
         writeln!(self.out, ");")?;
 
+        if guarded {
+            writeln!(self.out, "{level}}}")?;
+        }
+
         Ok(())
     }
 
     /// Helper method for writing an `ImageLoad` expression.
+    ///
+    /// Honors [`Self::policies`]`.image_load`: under [`Restrict`](proc::BoundsCheckPolicy::Restrict)
+    /// every coordinate/array index/sample/level argument is clamped into
+    /// range before the fetch; under
+    /// [`ReadZeroSkipWrite`](proc::BoundsCheckPolicy::ReadZeroSkipWrite) the
+    /// fetch is wrapped in a ternary that evaluates to a zero texel whenever
+    /// any argument is out of range. Storage images on non-ES targets are
+    /// left `Unchecked`, since desktop GL already guarantees out-of-range
+    /// `imageLoad`s return zero (GLES leaves the alpha component undefined).
     #[allow(clippy::too_many_arguments)]
     fn write_image_load(
         &mut self,
@@ -4594,8 +6954,14 @@ impl<'a, W: Write> Writer<'a, W> {
         // Get the name of the function to be used for the load operation
         // and the policy to be used with it.
         let (fun_name, policy) = match class {
-            // Sampled images inherit the policy from the user passed policies
-            crate::ImageClass::Sampled { .. } => ("texelFetch", self.policies.image_load),
+            // Sampled and depth images inherit the policy from the user passed policies.
+            // `texelFetch` on a depth sampler returns a scalar (or a `vec4` whose `.x`
+            // holds the depth value, depending on driver) rather than a `vec4`, which is
+            // handled below by suffixing the call with `.x` and using a scalar zero value
+            // for the `ReadZeroSkipWrite` policy.
+            crate::ImageClass::Sampled { .. } | crate::ImageClass::Depth { .. } => {
+                ("texelFetch", self.policies.image_load)
+            }
             crate::ImageClass::Storage { .. } => {
                 // OpenGL ES 3.1 mentions in Chapter "8.22 Texture Image Loads and Stores" that:
                 // "Invalid image loads will return a vector where the value of R, G, and B components
@@ -4612,12 +6978,6 @@ impl<'a, W: Write> Writer<'a, W> {
                 };
                 ("imageLoad", policy)
             }
-            // TODO: Is there even a function for this?
-            crate::ImageClass::Depth { multi: _ } => {
-                return Err(Error::Custom(
-                    "WGSL `textureLoad` from depth textures is not supported in GLSL".to_string(),
-                ))
-            }
         };
 
         // openGL es doesn't have 1D images so we need workaround it
@@ -4803,29 +7163,38 @@ impl<'a, W: Write> Writer<'a, W> {
         // Close the image load function.
         write!(self.out, ")")?;
 
+        // Depth images only expose their depth value in the `.x` (aka `.r`) component
+        // of the value `texelFetch` returns.
+        if matches!(class, crate::ImageClass::Depth { .. }) {
+            write!(self.out, ".x")?;
+        }
+
         // If we were using the `ReadZeroSkipWrite` policy we need to end the first branch
         // (which is taken if the condition is `true`) with a colon (`:`) and write the
         // second branch which is just a 0 value.
         if let proc::BoundsCheckPolicy::ReadZeroSkipWrite = policy {
-            // Get the kind of the output value.
-            let kind = match class {
-                // Only sampled images can reach here since storage images
-                // don't need bounds checks and depth images aren't implemented
-                crate::ImageClass::Sampled { kind, .. } => kind,
-                _ => unreachable!(),
-            };
-
             // End the first branch
             write!(self.out, " : ")?;
-            // Write the 0 value
-            write!(
-                self.out,
-                "{}vec4(",
-                glsl_scalar(crate::Scalar { kind, width: 4 })?.prefix,
-            )?;
-            self.write_zero_init_scalar(kind)?;
-            // Close the zero value constructor
-            write!(self.out, ")")?;
+
+            match class {
+                // Only sampled and depth images can reach here since storage images
+                // don't need bounds checks.
+                crate::ImageClass::Sampled { kind, .. } => {
+                    // Write the 0 value
+                    write!(
+                        self.out,
+                        "{}vec4(",
+                        glsl_scalar(crate::Scalar { kind, width: 4 })?.prefix,
+                    )?;
+                    self.write_zero_init_scalar(kind)?;
+                    // Close the zero value constructor
+                    write!(self.out, ")")?;
+                }
+                // The depth value extracted via `.x` above is always a float scalar.
+                crate::ImageClass::Depth { .. } => write!(self.out, "0.0")?,
+                _ => unreachable!(),
+            }
+
             // Close the parentheses surrounding our ternary
             write!(self.out, ")")?;
         }
@@ -4973,7 +7342,36 @@ impl<'a, W: Write> Writer<'a, W> {
     /// glsl allows adding both `readonly` and `writeonly` but this means that
     /// they can only be used to query information about the resource which isn't what
     /// we want here so when storage access is both `LOAD` and `STORE` add no modifiers
+    ///
+    /// Also writes the `coherent`/`restrict`/`volatile` memory qualifiers when forced on
+    /// via [`WriterFlags::FORCE_COHERENT_STORAGE`]/[`WriterFlags::FORCE_RESTRICT_STORAGE`]/
+    /// [`WriterFlags::FORCE_VOLATILE_STORAGE`]. These matter for correctness when a shader
+    /// does manual inter-invocation synchronization around storage images/buffers with
+    /// [`write_memory_barrier`](Self::write_memory_barrier)'s `memoryBarrierImage`/
+    /// `memoryBarrierBuffer`: without them the GLSL spec allows the compiler to cache
+    /// loads/stores across invocations, which `memoryBarrier*` alone doesn't forbid.
     fn write_storage_access(&mut self, storage_access: crate::StorageAccess) -> BackendResult {
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::FORCE_COHERENT_STORAGE)
+        {
+            write!(self.out, "coherent ")?;
+        }
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::FORCE_RESTRICT_STORAGE)
+        {
+            write!(self.out, "restrict ")?;
+        }
+        if self
+            .options
+            .writer_flags
+            .contains(WriterFlags::FORCE_VOLATILE_STORAGE)
+        {
+            write!(self.out, "volatile ")?;
+        }
         if storage_access.contains(crate::StorageAccess::ATOMIC) {
             return Ok(());
         }
@@ -4986,20 +7384,53 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Resolves the `dim`/`arrayed`/`multi`/`class` fields of a [`TextureMapping`] from
+    /// an image global's type, so the reflection pass doesn't force callers back into
+    /// `module.types` to learn how to set up the matching GL binding.
+    fn texture_mapping_params(
+        &self,
+        ty: Handle<crate::Type>,
+    ) -> (&'static str, bool, bool, TextureMappingClass) {
+        let (dim, arrayed, class) = match self.module.types[ty].inner {
+            TypeInner::Image { dim, arrayed, class } => (dim, arrayed, class),
+            _ => unreachable!(),
+        };
+
+        let (multi, mapping_class) = match class {
+            crate::ImageClass::Sampled { multi, .. } => (multi, TextureMappingClass::Sampled),
+            crate::ImageClass::Depth { multi } => (multi, TextureMappingClass::Depth),
+            crate::ImageClass::Storage { format, .. } => (
+                false,
+                TextureMappingClass::Storage {
+                    format: glsl_storage_format(format).ok(),
+                },
+            ),
+        };
+
+        (glsl_dimension(dim), arrayed, multi, mapping_class)
+    }
+
     /// Helper method used to produce the reflection info that's returned to the user
     fn collect_reflection_info(&mut self) -> Result<ReflectionInfo, Error> {
         let info = self.info.get_entry_point(self.entry_point_idx as usize);
         let mut texture_mapping = crate::FastHashMap::default();
         let mut uniforms = crate::FastHashMap::default();
+        let mut resource_bindings = crate::FastHashMap::default();
 
         for sampling in info.sampling_set.iter() {
             let tex_name = self.reflection_names_globals[&sampling.image].clone();
+            let image_ty = self.module.global_variables[sampling.image].ty;
+            let (dim, arrayed, multi, class) = self.texture_mapping_params(image_ty);
 
             match texture_mapping.entry(tex_name) {
                 hash_map::Entry::Vacant(v) => {
                     v.insert(TextureMapping {
                         texture: sampling.image,
                         sampler: Some(sampling.sampler),
+                        dim,
+                        arrayed,
+                        multi,
+                        class,
                     });
                 }
                 hash_map::Entry::Occupied(e) => {
@@ -5012,18 +7443,28 @@ impl<'a, W: Write> Writer<'a, W> {
         }
 
         let mut push_constant_info = None;
+        let mut buffer_info = Vec::new();
         for (handle, var) in self.module.global_variables.iter() {
             if info[handle].is_empty() {
                 continue;
             }
+            if let Some(ref binding) = var.binding {
+                resource_bindings.insert(handle, binding.clone());
+            }
+
             match self.module.types[var.ty].inner {
                 TypeInner::Image { .. } => {
                     let tex_name = self.reflection_names_globals[&handle].clone();
+                    let (dim, arrayed, multi, class) = self.texture_mapping_params(var.ty);
                     match texture_mapping.entry(tex_name) {
                         hash_map::Entry::Vacant(v) => {
                             v.insert(TextureMapping {
                                 texture: handle,
                                 sampler: None,
+                                dim,
+                                arrayed,
+                                multi,
+                                class,
                             });
                         }
                         hash_map::Entry::Occupied(_) => {
@@ -5035,6 +7476,12 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::AddressSpace::Uniform | crate::AddressSpace::Storage { .. } => {
                         let name = self.reflection_names_globals[&handle].clone();
                         uniforms.insert(handle, name);
+                        // The GLSL identifier a member is actually reached through is
+                        // `self.get_global_name(handle, var)`, not `name` above: for an
+                        // anonymous interface block (the common case) that's the block's
+                        // sole member name with no instance qualifier, while `name` is the
+                        // block type's own name, only useful for binding lookups.
+                        buffer_info.push((handle, self.get_global_name(handle, var), var.ty));
                     }
                     crate::AddressSpace::PushConstant => {
                         let name = self.reflection_names_globals[&handle].clone();
@@ -5045,17 +7492,25 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
+        // We don't have a layouter available to us, so we need to create one.
+        //
+        // This is potentially a bit wasteful, but the set of types in the program
+        // shouldn't be too large.
+        let mut layouter = proc::Layouter::default();
+        layouter.update(self.module.to_ctx()).unwrap();
+
+        let mut buffer_reflection = crate::FastHashMap::default();
+        for (handle, name, ty) in buffer_info {
+            let mut segments = vec![name];
+            let mut items = vec![];
+            self.collect_buffer_reflection_items(ty, &mut segments, &layouter, &mut 0, None, &mut items);
+            buffer_reflection.insert(handle, items);
+        }
+
         let mut push_constant_segments = Vec::new();
         let mut push_constant_items = vec![];
 
         if let Some((name, ty)) = push_constant_info {
-            // We don't have a layouter available to us, so we need to create one.
-            //
-            // This is potentially a bit wasteful, but the set of types in the program
-            // shouldn't be too large.
-            let mut layouter = proc::Layouter::default();
-            layouter.update(self.module.to_ctx()).unwrap();
-
             // We start with the name of the binding itself.
             push_constant_segments.push(name);
 
@@ -5075,6 +7530,9 @@ impl<'a, W: Write> Writer<'a, W> {
             varying: mem::take(&mut self.varying),
             push_constant_items,
             clip_distance_count: self.clip_distance_count,
+            resource_bindings,
+            lifted_interface_blocks: mem::take(&mut self.lifted_interface_blocks),
+            buffer_reflection,
         })
     }
 
@@ -5100,6 +7558,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     access_path: name,
                     offset: *offset,
                     ty,
+                    array: None,
                 });
                 *offset += layout.size;
             }
@@ -5109,11 +7568,37 @@ impl<'a, W: Write> Writer<'a, W> {
                     unreachable!("Cannot have dynamic arrays in push constants");
                 };
 
-                for i in 0..count.get() {
-                    // Add the array accessor and recurse.
-                    segments.push(format!("[{i}]"));
+                if self.options.compact_push_constant_arrays {
+                    // Emit a single item (or, for a struct/array base, a single item per
+                    // field) representing the `[0]` element, tagged with the count/stride
+                    // needed to upload the rest in one `glUniform*` call, instead of
+                    // unrolling every index into its own item.
+                    let start_offset = *offset;
+                    let base_layout = &layouter[base];
+                    let stride = base_layout.alignment.round_up(base_layout.size);
+
+                    let items_before = items.len();
+                    segments.push("[0]".to_string());
                     self.collect_push_constant_items(base, segments, layouter, offset, items);
                     segments.pop();
+
+                    let array = PushConstantArrayInfo {
+                        count: count.get(),
+                        stride,
+                    };
+                    for item in &mut items[items_before..] {
+                        item.array = Some(array);
+                    }
+
+                    // Account for the remaining `count - 1` elements without re-walking them.
+                    *offset = start_offset + stride * count.get();
+                } else {
+                    for i in 0..count.get() {
+                        // Add the array accessor and recurse.
+                        segments.push(format!("[{i}]"));
+                        self.collect_push_constant_items(base, segments, layouter, offset, items);
+                        segments.pop();
+                    }
                 }
 
                 // Ensure the stride is kept by rounding up to the alignment.
@@ -5136,6 +7621,197 @@ impl<'a, W: Write> Writer<'a, W> {
             _ => unreachable!(),
         }
     }
+
+    /// Generalization of [`Self::collect_push_constant_items`] for `Uniform`/`Storage`
+    /// buffers: same recursive std140/std430 walk, but also handling
+    /// dynamically-sized arrays (only their first element is enumerated, tagged with
+    /// `array_stride` so a host can derive further indices itself) and recording
+    /// `array_stride`/`matrix_stride` on every item produced.
+    fn collect_buffer_reflection_items(
+        &mut self,
+        ty: Handle<crate::Type>,
+        segments: &mut Vec<String>,
+        layouter: &proc::Layouter,
+        offset: &mut u32,
+        array_stride: Option<u32>,
+        items: &mut Vec<BufferReflectionItem>,
+    ) {
+        // At this point in the recursion, `segments` contains the path
+        // needed to access `ty` from the root.
+
+        let layout = &layouter[ty];
+        *offset = layout.alignment.round_up(*offset);
+        match self.module.types[ty].inner {
+            TypeInner::Scalar { .. } | TypeInner::Vector { .. } => {
+                let name: String = segments.iter().map(String::as_str).collect();
+                items.push(BufferReflectionItem {
+                    access_path: name,
+                    ty,
+                    offset: *offset,
+                    array_stride,
+                    matrix_stride: None,
+                });
+                *offset += layout.size;
+            }
+            TypeInner::Matrix { columns, .. } => {
+                let name: String = segments.iter().map(String::as_str).collect();
+                items.push(BufferReflectionItem {
+                    access_path: name,
+                    ty,
+                    offset: *offset,
+                    array_stride,
+                    // In std140/std430 a matrix is laid out as `columns` column
+                    // vectors, each padded to the same stride, so the total size
+                    // the layouter already computed divides evenly by the column
+                    // count.
+                    matrix_stride: Some(layout.size / columns as u32),
+                });
+                *offset += layout.size;
+            }
+            // Arrays are recursed into. Unlike push constants, buffers can have a
+            // dynamically-sized trailing array, whose element count isn't known until
+            // draw time; in that case only the `[0]` element is enumerated; its items
+            // are still tagged with the per-element stride so callers can compute
+            // further indices themselves.
+            TypeInner::Array { base, size, .. } => {
+                let base_layout = &layouter[base];
+                let stride = base_layout.alignment.round_up(base_layout.size);
+
+                let count = match size {
+                    crate::ArraySize::Constant(count) => count.get(),
+                    _ => 1,
+                };
+
+                for i in 0..count {
+                    // Add the array accessor and recurse.
+                    segments.push(format!("[{i}]"));
+                    self.collect_buffer_reflection_items(
+                        base,
+                        segments,
+                        layouter,
+                        offset,
+                        Some(stride),
+                        items,
+                    );
+                    segments.pop();
+                }
+
+                // Ensure the stride is kept by rounding up to the alignment.
+                *offset = layout.alignment.round_up(*offset)
+            }
+            TypeInner::Struct { ref members, .. } => {
+                for (index, member) in members.iter().enumerate() {
+                    // Add struct accessor and recurse.
+                    segments.push(format!(
+                        ".{}",
+                        self.names[&NameKey::StructMember(ty, index as u32)]
+                    ));
+                    self.collect_buffer_reflection_items(
+                        member.ty,
+                        segments,
+                        layouter,
+                        offset,
+                        array_stride,
+                        items,
+                    );
+                    segments.pop();
+                }
+
+                // Ensure ending padding is kept by rounding up to the alignment.
+                *offset = layout.alignment.round_up(*offset)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Accumulates the extension-relevant constructs found while walking a
+/// function body in [`collect_statement_extension_needs`].
+#[derive(Default)]
+struct StatementExtensionNeeds {
+    /// The `GL_KHR_shader_subgroup_*` extensions each subgroup statement
+    /// found needs. Does not include the `basic` extension that every
+    /// subgroup extension depends on; callers should add it themselves if
+    /// this ends up non-empty.
+    subgroup_families: alloc::collections::BTreeSet<&'static str>,
+    /// Whether any [`crate::Statement::RayQuery`] was found, meaning
+    /// `GL_EXT_ray_query` is required.
+    uses_ray_query: bool,
+}
+
+/// Recursively walks `stmt` (and any nested blocks it contains), recording
+/// the extensions it needs into `needs`.
+fn collect_statement_extension_needs(stmt: &crate::Statement, needs: &mut StatementExtensionNeeds) {
+    use crate::Statement as S;
+
+    match *stmt {
+        S::Block(ref block) => {
+            for sta in block.iter() {
+                collect_statement_extension_needs(sta, needs);
+            }
+        }
+        S::If {
+            ref accept,
+            ref reject,
+            ..
+        } => {
+            for sta in accept.iter().chain(reject.iter()) {
+                collect_statement_extension_needs(sta, needs);
+            }
+        }
+        S::Switch { ref cases, .. } => {
+            for case in cases.iter() {
+                for sta in case.body.iter() {
+                    collect_statement_extension_needs(sta, needs);
+                }
+            }
+        }
+        S::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => {
+            for sta in body.iter().chain(continuing.iter()) {
+                collect_statement_extension_needs(sta, needs);
+            }
+        }
+        S::SubgroupBallot { .. } => {
+            needs.subgroup_families.insert("GL_KHR_shader_subgroup_ballot");
+        }
+        S::SubgroupCollectiveOperation { op, .. } => {
+            needs.subgroup_families.insert(match op {
+                crate::SubgroupOperation::All | crate::SubgroupOperation::Any => {
+                    "GL_KHR_shader_subgroup_vote"
+                }
+                crate::SubgroupOperation::Add
+                | crate::SubgroupOperation::Mul
+                | crate::SubgroupOperation::Max
+                | crate::SubgroupOperation::Min
+                | crate::SubgroupOperation::And
+                | crate::SubgroupOperation::Or
+                | crate::SubgroupOperation::Xor => "GL_KHR_shader_subgroup_arithmetic",
+            });
+        }
+        S::SubgroupGather { mode, .. } => {
+            needs.subgroup_families.insert(match mode {
+                crate::GatherMode::BroadcastFirst | crate::GatherMode::Broadcast(_) => {
+                    "GL_KHR_shader_subgroup_ballot"
+                }
+                crate::GatherMode::Shuffle(_)
+                | crate::GatherMode::ShuffleXor(_) => "GL_KHR_shader_subgroup_shuffle",
+                crate::GatherMode::ShuffleDown(_) | crate::GatherMode::ShuffleUp(_) => {
+                    "GL_KHR_shader_subgroup_shuffle_relative"
+                }
+                crate::GatherMode::QuadBroadcast(_) | crate::GatherMode::QuadSwap(_) => {
+                    "GL_KHR_shader_subgroup_quad"
+                }
+            });
+        }
+        S::RayQuery { .. } => {
+            needs.uses_ray_query = true;
+        }
+        _ => {}
+    }
 }
 
 /// Structure returned by [`glsl_scalar`]
@@ -5153,20 +7829,42 @@ struct ScalarString<'a> {
 /// Check [`ScalarString`] for the information provided
 ///
 /// # Errors
-/// If a [`Float`](crate::ScalarKind::Float) with an width that isn't 4 or 8
+/// If a [`Float`](crate::ScalarKind::Float) with a width that isn't 2, 4 or 8,
+/// a [`Sint`](crate::ScalarKind::Sint)/[`Uint`](crate::ScalarKind::Uint) with a
+/// width that isn't 4 or 8, or an abstract scalar
 const fn glsl_scalar(scalar: crate::Scalar) -> Result<ScalarString<'static>, Error> {
     use crate::ScalarKind as Sk;
 
     Ok(match scalar.kind {
-        Sk::Sint => ScalarString {
-            prefix: "i",
-            full: "int",
+        Sk::Sint => match scalar.width {
+            4 => ScalarString {
+                prefix: "i",
+                full: "int",
+            },
+            // Requires `GL_ARB_gpu_shader_int64`; see
+            // `Version::supports_shader_int64` and `WriterFlags::SHADER_INT64`.
+            8 => ScalarString {
+                prefix: "i64",
+                full: "int64_t",
+            },
+            _ => return Err(Error::UnsupportedScalar(scalar)),
         },
-        Sk::Uint => ScalarString {
-            prefix: "u",
-            full: "uint",
+        Sk::Uint => match scalar.width {
+            4 => ScalarString {
+                prefix: "u",
+                full: "uint",
+            },
+            8 => ScalarString {
+                prefix: "u64",
+                full: "uint64_t",
+            },
+            _ => return Err(Error::UnsupportedScalar(scalar)),
         },
         Sk::Float => match scalar.width {
+            2 => ScalarString {
+                prefix: "f16",
+                full: "float16_t",
+            },
             4 => ScalarString {
                 prefix: "",
                 full: "float",
@@ -5199,8 +7897,10 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
                 "gl_FragCoord"
             }
         }
-        Bi::ViewIndex if options.targeting_webgl => "int(gl_ViewID_OVR)",
-        Bi::ViewIndex => "gl_ViewIndex",
+        // `GL_OVR_multiview2` (requested explicitly off WebGL, implicit on WebGL via
+        // the WebXR contract) provides `gl_ViewID_OVR`, not `GL_EXT_multiview`'s
+        // `gl_ViewIndex`, so both targets read the same built-in.
+        Bi::ViewIndex => "int(gl_ViewID_OVR)",
         // vertex
         Bi::BaseInstance => "uint(gl_BaseInstance)",
         Bi::BaseVertex => "uint(gl_BaseVertex)",
@@ -5295,6 +7995,29 @@ const fn glsl_dimension(dim: crate::ImageDimension) -> &'static str {
     }
 }
 
+/// Maps a `RayIntersection` struct member's name to the `GL_EXT_ray_query`
+/// built-in that computes it, or `None` if the name isn't recognized.
+fn ray_intersection_builtin(field_name: &str) -> Option<&'static str> {
+    Some(match field_name {
+        "kind" => "rayQueryGetIntersectionTypeEXT",
+        "t" => "rayQueryGetIntersectionTEXT",
+        "instance_custom_index" | "instance_custom_data" => {
+            "rayQueryGetIntersectionInstanceCustomIndexEXT"
+        }
+        "instance_id" | "instance_index" => "rayQueryGetIntersectionInstanceIdEXT",
+        "sbt_record_offset" | "instance_shader_binding_table_record_offset" => {
+            "rayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetEXT"
+        }
+        "geometry_index" => "rayQueryGetIntersectionGeometryIndexEXT",
+        "primitive_index" => "rayQueryGetIntersectionPrimitiveIndexEXT",
+        "barycentrics" => "rayQueryGetIntersectionBarycentricsEXT",
+        "front_face" => "rayQueryGetIntersectionFrontFaceEXT",
+        "object_to_world" => "rayQueryGetIntersectionObjectToWorldEXT",
+        "world_to_object" => "rayQueryGetIntersectionWorldToObjectEXT",
+        _ => return None,
+    })
+}
+
 /// Helper function that returns the glsl storage format string of [`StorageFormat`](crate::StorageFormat)
 fn glsl_storage_format(format: crate::StorageFormat) -> Result<&'static str, Error> {
     use crate::StorageFormat as Sf;